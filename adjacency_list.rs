@@ -0,0 +1,170 @@
+/*
+ * Christopher Piraino
+ *
+ * Adjacency list implementation of the
+ * Graph trait, for graphs that are too
+ * sparse to store as a matrix.
+ */
+use std::vec::Vec;
+use std::result::Result;
+use graph::{Graph, MutableGraph, DynamicGraph, check_bounds, removal_remap};
+#[test]
+use graph::{Continue, AdjacencyMatrix, search_graph};
+
+pub struct AdjacencyList<W> {
+    // Outgoing edges of each vertex as (target, value) pairs, sorted by target
+    // so that neighbors are reported in the same order as VectorMatrix.
+    edges: Vec<Vec<(uint, W)>>
+}
+
+// Private methods on AdjacencyList.
+impl<W> AdjacencyList<W> {
+    fn check_bounds(&self, x: uint, y: uint) -> Result<(), ~str> {
+        check_bounds(self.edges.len(), x, y)
+    }
+    // Index of the edge (x, y) in the list of x, or the index at which it
+    // would have to be inserted to keep the list sorted.
+    fn search(&self, x: uint, y: uint) -> Result<uint, uint> {
+        let list = self.edges.get(x);
+        for (i, &(v, _)) in list.iter().enumerate() {
+            if v == y {
+                return Ok(i);
+            } else if v > y {
+                return Err(i);
+            }
+        }
+        Err(list.len())
+    }
+}
+
+impl<W: Clone> Graph<W> for AdjacencyList<W> {
+    fn vertices(&self) -> uint {
+        self.edges.len()
+    }
+    fn adjacent(&self, x: uint, y: uint) -> Result<bool, ~str> {
+        try!(self.check_bounds(x, y));
+        Ok(self.search(x, y).is_ok())
+    }
+    fn neighbors(&self, x: uint) -> Vec<uint> {
+        self.edges.get(x).iter().map(|&(v, _)| v).collect()
+    }
+    fn get_edge_value(&self, x: uint, y: uint) -> Result<Option<W>, ~str> {
+        try!(self.check_bounds(x, y));
+        match self.search(x, y) {
+            Ok(i) => {
+                let &(_, ref val) = self.edges.get(x).get(i);
+                Ok(Some(val.clone()))
+            }
+            Err(_) => Ok(None)
+        }
+    }
+}
+
+impl<W: Clone> MutableGraph<W> for AdjacencyList<W> {
+    fn new(vertices: uint) -> ~AdjacencyList<W> {
+        ~AdjacencyList { edges: Vec::from_fn(vertices, |_| Vec::new()) }
+    }
+    fn add(&mut self, x: uint, y: uint, val: W) -> Result<W, ~str> {
+        try!(self.check_bounds(x, y));
+        match self.search(x, y) {
+            Ok(i) => *self.edges.get_mut(x).get_mut(i) = (y, val.clone()),
+            Err(i) => self.edges.get_mut(x).insert(i, (y, val.clone()))
+        }
+        Ok(val)
+    }
+    fn delete(&mut self, x: uint, y: uint) -> Result<Option<W>, ~str> {
+        try!(self.check_bounds(x, y));
+        match self.search(x, y) {
+            Ok(i) => Ok(self.edges.get_mut(x).remove(i).map(|(_, val)| val)),
+            Err(_) => Ok(None)
+        }
+    }
+    fn set_edge_value(&mut self, x: uint, y: uint, val: W) -> Result<W, ~str> {
+        try!(self.check_bounds(x, y));
+        match self.search(x, y) {
+            Ok(_) => self.add(x, y, val),
+            Err(_) => Err(format!("({}, {}) is not an edge.", x, y))
+        }
+    }
+}
+
+impl<W: Clone> DynamicGraph<W> for AdjacencyList<W> {
+    fn add_vertex(&mut self) -> uint {
+        self.edges.push(Vec::new());
+        self.edges.len() - 1
+    }
+    // Renumbering the targets keeps every list sorted.
+    fn remove_vertex(&mut self, v: uint) -> Result<Vec<Option<uint>>, ~str> {
+        try!(self.check_bounds(v, v));
+        let vertices = self.edges.len();
+        self.edges.remove(v);
+        for list in self.edges.mut_iter() {
+            list.retain(|&(y, _)| y != v);
+            for edge in list.mut_iter() {
+                if *edge.ref0() > v {
+                    *edge.mut0() -= 1;
+                }
+            }
+        }
+        Ok(removal_remap(vertices, v))
+    }
+}
+
+#[test]
+fn test_adjacency_list_add_delete() {
+    let mut graph: ~AdjacencyList<int> = MutableGraph::new(3);
+    assert!(!graph.adjacent(0, 2).ok().unwrap());
+    assert!(graph.add(0,2,5).is_ok());
+    assert!(graph.add(0,1,3).is_ok());
+    assert!(graph.adjacent(0, 2).ok().unwrap());
+    assert_eq!(graph.neighbors(0), vec!(1u, 2));
+    assert_eq!(graph.get_edge_value(0, 2).ok().unwrap(), Some(5));
+    assert_eq!(graph.delete(0, 2).ok().unwrap(), Some(5));
+    assert!(!graph.adjacent(0, 2).ok().unwrap());
+    assert_eq!(graph.get_edge_value(0, 2).ok().unwrap(), None);
+    assert!(graph.add(0,2,0).is_ok());
+    assert!(graph.adjacent(0, 2).ok().unwrap());
+    assert!(graph.delete(0, 2).is_ok());
+    assert_eq!(graph.neighbors(0), vec!(1u));
+}
+
+#[test]
+fn test_adjacency_list_bounds() {
+    let mut graph: ~AdjacencyList<int> = MutableGraph::new(2);
+    assert!(graph.add(0,2,1).is_err());
+    assert!(graph.adjacent(2,0).is_err());
+    assert!(graph.set_edge_value(0,1,4).is_err());
+    assert!(graph.add(0,1,1).is_ok());
+    assert!(graph.set_edge_value(0,1,4).is_ok());
+    assert_eq!(graph.get_edge_value(0,1).ok().unwrap(), Some(4));
+}
+
+#[test]
+fn test_adjacency_list_matches_matrix() {
+    let list: ~AdjacencyList<int> = search_graph();
+    let matrix: ~AdjacencyMatrix<int> = search_graph();
+    let mut list_dfs = Vec::new();
+    let mut matrix_dfs = Vec::new();
+    list.depth_first_search(|_, v| { list_dfs.push(v); Continue }, 0);
+    matrix.depth_first_search(|_, v| { matrix_dfs.push(v); Continue }, 0);
+    assert_eq!(list_dfs, matrix_dfs);
+    let mut list_bfs = Vec::new();
+    let mut matrix_bfs = Vec::new();
+    list.breadth_first_search(|_, v| { list_bfs.push(v); Continue }, 0);
+    matrix.breadth_first_search(|_, v| { matrix_bfs.push(v); Continue }, 0);
+    assert_eq!(list_bfs, matrix_bfs);
+}
+
+#[test]
+fn test_adjacency_list_add_remove_vertex() {
+    let mut list: ~AdjacencyList<int> = search_graph();
+    let mut matrix: ~AdjacencyMatrix<int> = search_graph();
+    assert_eq!(list.add_vertex(), matrix.add_vertex());
+    assert!(list.add(5, 2, 1).is_ok());
+    assert!(matrix.add(5, 2, 1).is_ok());
+    assert_eq!(list.remove_vertex(1).ok(), matrix.remove_vertex(1).ok());
+    for v in range(0u, 5) {
+        assert_eq!(list.neighbors(v), matrix.neighbors(v));
+    }
+    assert!(list.remove_vertex(5).is_err());
+}
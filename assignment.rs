@@ -0,0 +1,177 @@
+/*
+ * Christopher Piraino
+ *
+ * The assignment problem, solved with the
+ * Hungarian algorithm over a cost matrix.
+ */
+use std::vec::Vec;
+use std::num::Zero;
+use graph::{Matrix, Weight, improves};
+#[test]
+use graph::VectorMatrix;
+#[test]
+use std::default::Default;
+
+#[deriving(Eq, Show)]
+pub enum Objective {
+    Minimize,
+    Maximize
+}
+
+/*  An assignment of rows to columns as (row, column) pairs, ordered by row,
+ *  along with its total cost. Every row is assigned if there are no more rows
+ *  than columns, and every column otherwise.
+ */
+pub struct Assignment<W> {
+    pub pairs: Vec<(uint, uint)>,
+    pub cost: W
+}
+
+/*  The Hungarian (Kuhn-Munkres) algorithm in O(n^2 m) time for n rows and m
+ *  columns. Rows are added one at a time, each by a Dijkstra-like search for
+ *  the cheapest way to reassign the columns using the reduced costs
+ *  c(i, j) - u(i) - v(j), which the row and column potentials u and v keep
+ *  non-negative. A matrix with more rows than columns is solved transposed, and
+ *  maximizing c is solved as minimizing max - c, which stays non-negative for
+ *  unsigned costs.
+ */
+pub fn hungarian<W: Weight, M: Matrix<W>>(matrix: &M, objective: Objective) -> Assignment<W> {
+    let transposed = matrix.height() > matrix.width();
+    let (n, m) = if transposed {
+        (matrix.width(), matrix.height())
+    } else {
+        (matrix.height(), matrix.width())
+    };
+    let mut cost: Vec<Vec<W>> = Vec::from_fn(n, |i| Vec::from_fn(m, |j| {
+        if transposed { matrix.at(j, i).unwrap() } else { matrix.at(i, j).unwrap() }
+    }));
+    if objective == Maximize {
+        let mut largest: Option<W> = None;
+        for row in cost.iter() {
+            for c in row.iter() {
+                if largest.as_ref().map_or(true, |top| *c > *top) {
+                    largest = Some(c.clone());
+                }
+            }
+        }
+        match largest {
+            Some(top) => for row in cost.mut_iter() {
+                for c in row.mut_iter() {
+                    *c = top - *c;
+                }
+            },
+            None => {}
+        }
+    }
+    let zero: W = Zero::zero();
+    // Rows and columns are numbered from 1, and column 0 stands for the row
+    // being added. owner[j] is the row assigned to column j, or 0 for none.
+    let mut u: Vec<W> = Vec::from_elem(n + 1, zero.clone());
+    let mut v: Vec<W> = Vec::from_elem(m + 1, zero.clone());
+    let mut owner = Vec::from_elem(m + 1, 0u);
+    let mut way = Vec::from_elem(m + 1, 0u);
+    for i in range(1, n + 1) {
+        *owner.get_mut(0) = i;
+        let mut j0 = 0u;
+        // The smallest reduced cost of reaching each column, None if not reached yet.
+        let mut reach: Vec<Option<W>> = Vec::from_elem(m + 1, None);
+        let mut used = Vec::from_elem(m + 1, false);
+        loop {
+            *used.get_mut(j0) = true;
+            let i0 = *owner.get(j0);
+            let mut delta: Option<W> = None;
+            let mut j1 = 0u;
+            for j in range(1, m + 1) {
+                if *used.get(j) {
+                    continue;
+                }
+                let reduced = *cost.get(i0 - 1).get(j - 1) - *u.get(i0) - *v.get(j);
+                if improves(&reduced, reach.get(j)) {
+                    *reach.get_mut(j) = Some(reduced);
+                    *way.get_mut(j) = j0;
+                }
+                if improves(reach.get(j).get_ref(), &delta) {
+                    delta = reach.get(j).clone();
+                    j1 = j;
+                }
+            }
+            let delta = delta.unwrap();
+            for j in range(0, m + 1) {
+                if *used.get(j) {
+                    let row = *owner.get(j);
+                    let raised = *u.get(row) + delta;
+                    *u.get_mut(row) = raised;
+                    let lowered = *v.get(j) - delta;
+                    *v.get_mut(j) = lowered;
+                } else {
+                    let lowered = reach.get(j).clone().unwrap() - delta;
+                    *reach.get_mut(j) = Some(lowered);
+                }
+            }
+            j0 = j1;
+            if *owner.get(j0) == 0 {
+                break;
+            }
+        }
+        // Shift the assignments back along the path that reached a free column.
+        loop {
+            let j1 = *way.get(j0);
+            let row = *owner.get(j1);
+            *owner.get_mut(j0) = row;
+            j0 = j1;
+            if j0 == 0 {
+                break;
+            }
+        }
+    }
+    let mut pairs = Vec::with_capacity(n);
+    for j in range(1, m + 1) {
+        let i = *owner.get(j);
+        if i != 0 {
+            pairs.push(if transposed { (j - 1, i - 1) } else { (i - 1, j - 1) });
+        }
+    }
+    pairs.as_mut_slice().sort();
+    let mut total = zero;
+    for &(row, col) in pairs.iter() {
+        total = total + matrix.at(row, col).unwrap();
+    }
+    Assignment { pairs: pairs, cost: total }
+}
+
+#[cfg(test)]
+fn cost_matrix<W: Clone + Default>(rows: &[&[W]]) -> ~VectorMatrix<W> {
+    let mut matrix: ~VectorMatrix<W> = Matrix::zero(rows.len(), rows[0].len());
+    for (i, row) in rows.iter().enumerate() {
+        for (j, c) in row.iter().enumerate() {
+            assert!(matrix.set(i, j, c.clone()).is_ok());
+        }
+    }
+    matrix
+}
+
+#[test]
+fn test_hungarian_square() {
+    let matrix = cost_matrix(&[&[4, 1, 3], &[2, 0, 5], &[3, 2, 2]]);
+    let cheapest = hungarian(&*matrix, Minimize);
+    assert_eq!(cheapest.pairs, vec!((0u, 1u), (1, 0), (2, 2)));
+    assert_eq!(cheapest.cost, 5);
+    let dearest = hungarian(&*matrix, Maximize);
+    assert_eq!(dearest.pairs, vec!((0u, 0u), (1, 2), (2, 1)));
+    assert_eq!(dearest.cost, 11);
+}
+
+#[test]
+fn test_hungarian_rectangular() {
+    let wide = cost_matrix(&[&[1, 5, 3], &[4, 2, 6]]);
+    let assignment = hungarian(&*wide, Minimize);
+    assert_eq!(assignment.pairs, vec!((0u, 0u), (1, 1)));
+    assert_eq!(assignment.cost, 3);
+    let tall = cost_matrix(&[&[1u, 4], &[5, 2], &[3, 6]]);
+    let assignment = hungarian(&*tall, Minimize);
+    assert_eq!(assignment.pairs, vec!((0u, 0u), (1, 1)));
+    assert_eq!(assignment.cost, 3);
+    let assignment = hungarian(&*tall, Maximize);
+    assert_eq!(assignment.pairs, vec!((1u, 0u), (2, 1)));
+    assert_eq!(assignment.cost, 11);
+}
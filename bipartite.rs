@@ -0,0 +1,264 @@
+/*
+ * Christopher Piraino
+ *
+ * Bipartite graphs, whose vertices split
+ * into two sides with every edge going
+ * from one side to the other.
+ */
+use std::vec::Vec;
+use std::result::Result;
+use graph::{Graph, MutableGraph, check_bounds};
+use adjacency_list::AdjacencyList;
+#[test]
+use undirected::UndirectedMatrix;
+
+#[deriving(Eq, Show, Clone)]
+pub enum Side {
+    Left,
+    Right
+}
+
+impl Side {
+    pub fn opposite(&self) -> Side {
+        match *self {
+            Left => Right,
+            Right => Left
+        }
+    }
+}
+
+/*  An undirected graph with the sides fixed when it is created. The left
+ *  vertices are numbered 0..left and the right ones left..left+right, and
+ *  edges are added by their position within each side, so an edge can never
+ *  join two vertices on the same side. Every edge is stored in both
+ *  directions.
+ */
+pub struct BipartiteGraph<W> {
+    left: uint,
+    right: uint,
+    edges: ~AdjacencyList<W>
+}
+
+impl<W: Clone> BipartiteGraph<W> {
+    pub fn new(left: uint, right: uint) -> BipartiteGraph<W> {
+        BipartiteGraph { left: left, right: right, edges: MutableGraph::new(left + right) }
+    }
+    // The number of vertices on a side.
+    pub fn size(&self, side: Side) -> uint {
+        match side {
+            Left => self.left,
+            Right => self.right
+        }
+    }
+    // The vertex index of the ith vertex on a side.
+    pub fn vertex(&self, side: Side, i: uint) -> uint {
+        match side {
+            Left => i,
+            Right => self.left + i
+        }
+    }
+    // The side of a vertex and its position within that side.
+    pub fn side(&self, v: uint) -> (Side, uint) {
+        if v < self.left { (Left, v) } else { (Right, v - self.left) }
+    }
+    // The side of every vertex, as fixed when the graph was created. Unlike
+    // the coloring found by is_bipartite, this puts isolated vertices on the
+    // side they were declared on.
+    pub fn partition(&self) -> Vec<Side> {
+        range(0, self.left + self.right).map(|v| self.side(v).val0()).collect()
+    }
+    // Adds an edge between the lth left vertex and the rth right vertex.
+    pub fn add(&mut self, l: uint, r: uint, val: W) -> Result<W, ~str> {
+        let (x, y) = try!(self.edge_vertices(l, r));
+        try!(self.edges.add(y, x, val.clone()));
+        self.edges.add(x, y, val)
+    }
+    pub fn delete(&mut self, l: uint, r: uint) -> Result<Option<W>, ~str> {
+        let (x, y) = try!(self.edge_vertices(l, r));
+        try!(self.edges.delete(y, x));
+        self.edges.delete(x, y)
+    }
+    pub fn set_edge_value(&mut self, l: uint, r: uint, val: W) -> Result<W, ~str> {
+        let (x, y) = try!(self.edge_vertices(l, r));
+        try!(self.edges.set_edge_value(y, x, val.clone()));
+        self.edges.set_edge_value(x, y, val)
+    }
+    fn edge_vertices(&self, l: uint, r: uint) -> Result<(uint, uint), ~str> {
+        if l >= self.left || r >= self.right {
+            Err(format!("({}, {}) is out of bounds, graph has {} left and {} right vertices.",
+                        l, r, self.left, self.right))
+        } else {
+            Ok((self.vertex(Left, l), self.vertex(Right, r)))
+        }
+    }
+}
+
+impl<W: Clone> Graph<W> for BipartiteGraph<W> {
+    fn vertices(&self) -> uint {
+        self.left + self.right
+    }
+    fn adjacent(&self, x: uint, y: uint) -> Result<bool, ~str> {
+        self.edges.adjacent(x, y)
+    }
+    fn neighbors(&self, x: uint) -> Vec<uint> {
+        self.edges.neighbors(x)
+    }
+    fn get_edge_value(&self, x: uint, y: uint) -> Result<Option<W>, ~str> {
+        self.edges.get_edge_value(x, y)
+    }
+}
+
+/*  The neighbors of every vertex with each edge taken in both directions,
+ *  whichever way it is stored, sorted and without repeats.
+ */
+pub fn undirected_adjacency<W, G: Graph<W>>(graph: &G) -> Vec<Vec<uint>> {
+    let vertices = graph.vertices();
+    let mut adj: Vec<Vec<uint>> = Vec::from_fn(vertices, |_| Vec::new());
+    for u in range(0, vertices) {
+        for &v in graph.neighbors(u).iter() {
+            adj.get_mut(u).push(v);
+            adj.get_mut(v).push(u);
+        }
+    }
+    for list in adj.mut_iter() {
+        list.as_mut_slice().sort();
+        list.dedup();
+    }
+    adj
+}
+
+// A graph seen through undirected_adjacency, so that the shared traversals
+// reach every edge from both of its ends.
+struct Undirected {
+    adj: Vec<Vec<uint>>
+}
+
+impl Graph<()> for Undirected {
+    fn vertices(&self) -> uint {
+        self.adj.len()
+    }
+    fn adjacent(&self, x: uint, y: uint) -> Result<bool, ~str> {
+        try!(check_bounds(self.adj.len(), x, y));
+        Ok(self.adj.get(x).contains(&y))
+    }
+    fn neighbors(&self, x: uint) -> Vec<uint> {
+        self.adj.get(x).clone()
+    }
+    fn get_edge_value(&self, x: uint, y: uint) -> Result<Option<()>, ~str> {
+        let adjacent = try!(self.adjacent(x, y));
+        Ok(if adjacent { Some(()) } else { None })
+    }
+}
+
+/*  Checks whether a graph is bipartite, treating every edge as undirected so
+ *  that it does not matter whether edges are stored in one direction or both.
+ *  A breadth first search from each uncolored vertex puts the vertices at even
+ *  depths on the left and those at odd depths on the right. If that coloring is
+ *  proper it is returned, and otherwise an edge joining two vertices of the
+ *  same color closes an odd cycle through the search tree, which is returned
+ *  in the order of its edges. Isolated vertices are always colored left, so
+ *  the sides declared for a BipartiteGraph are better read from partition.
+ */
+pub fn is_bipartite<W, G: Graph<W>>(graph: &G) -> Result<Vec<Side>, Vec<uint>> {
+    let undirected = Undirected { adj: undirected_adjacency(graph) };
+    let vertices = undirected.vertices();
+    let mut depth: Vec<Option<uint>> = Vec::from_elem(vertices, None);
+    let mut parent: Vec<Option<uint>> = Vec::from_elem(vertices, None);
+    for root in range(0, vertices) {
+        if depth.get(root).is_some() {
+            continue;
+        }
+        for (v, d, p) in undirected.bfs_iter(root) {
+            *depth.get_mut(v) = Some(d);
+            *parent.get_mut(v) = p;
+        }
+    }
+    let depth: Vec<uint> = depth.iter().map(|d| d.unwrap()).collect();
+    for u in range(0, vertices) {
+        for &v in undirected.adj.get(u).iter() {
+            if *depth.get(u) % 2 == *depth.get(v) % 2 {
+                return Err(odd_cycle(&depth, &parent, u, v));
+            }
+        }
+    }
+    Ok(depth.iter().map(|&d| if d % 2 == 0 { Left } else { Right }).collect())
+}
+
+// The tree paths from u and v up to where they meet, joined by the edge (u, v).
+fn odd_cycle(depth: &Vec<uint>, parent: &Vec<Option<uint>>, u: uint, v: uint) -> Vec<uint> {
+    let mut from_u = vec!(u);
+    let mut from_v = vec!(v);
+    let (mut a, mut b) = (u, v);
+    while a != b {
+        if *depth.get(a) >= *depth.get(b) {
+            a = (*parent.get(a)).unwrap();
+            from_u.push(a);
+        } else {
+            b = (*parent.get(b)).unwrap();
+            from_v.push(b);
+        }
+    }
+    from_v.pop();
+    from_v.reverse();
+    from_u.push_all_move(from_v);
+    from_u
+}
+
+#[test]
+fn test_bipartite_graph() {
+    let mut graph = BipartiteGraph::new(2, 3);
+    assert!(graph.add(0, 2, 1).is_ok());
+    assert!(graph.add(1, 0, 1).is_ok());
+    assert!(graph.add(1, 2, 1).is_ok());
+    assert!(graph.add(2, 0, 1).is_err());
+    assert_eq!(graph.vertices(), 5);
+    assert_eq!(graph.vertex(Right, 2), 4);
+    assert_eq!(graph.side(4), (Right, 2));
+    assert_eq!(graph.neighbors(4), vec!(0u, 1));
+    assert_eq!(graph.neighbors(1), vec!(2u, 4));
+    assert!(graph.set_edge_value(0, 2, 7).is_ok());
+    assert_eq!(graph.get_edge_value(4, 0).ok().unwrap(), Some(7));
+    assert_eq!(graph.delete(1, 0).ok().unwrap(), Some(1));
+    assert!(!graph.adjacent(2, 1).ok().unwrap());
+    // Vertices 2 and 3 are isolated, so each starts a search of its own and
+    // is colored left.
+    let coloring = is_bipartite(&graph).ok().unwrap();
+    assert_eq!(coloring, vec!(Left, Left, Left, Left, Right));
+    assert_eq!(graph.partition(), vec!(Left, Left, Right, Right, Right));
+}
+
+#[test]
+fn test_is_bipartite_odd_cycle() {
+    // An even cycle 0 - 1 - 2 - 3 - 0 with a tail 3 - 4 - 5.
+    let mut graph: ~UndirectedMatrix<int> = MutableGraph::new(6);
+    let edges = [(0u,1u), (1,2), (2,3), (3,0), (3,4), (4,5)];
+    for &(x, y) in edges.iter() {
+        assert!(graph.add(x, y, 1).is_ok());
+    }
+    assert_eq!(is_bipartite(&*graph).ok().unwrap(),
+               vec!(Left, Right, Left, Right, Left, Right));
+    // Closing 0 - 3 - 4 - 0 makes an odd cycle.
+    assert!(graph.add(4, 0, 1).is_ok());
+    let cycle = is_bipartite(&*graph).err().unwrap();
+    assert!(cycle.len() % 2 == 1);
+    for i in range(0, cycle.len()) {
+        let next = *cycle.get((i + 1) % cycle.len());
+        assert!(graph.adjacent(*cycle.get(i), next).ok().unwrap());
+    }
+    let mut looped: ~UndirectedMatrix<int> = MutableGraph::new(2);
+    assert!(looped.add(1, 1, 1).is_ok());
+    assert_eq!(is_bipartite(&*looped).err().unwrap(), vec!(1u));
+}
+
+#[test]
+fn test_is_bipartite_mixed_directions() {
+    // The path 0 - 1 - 3 - 2, stored as 0 -> 1, 2 -> 3 and 3 -> 1.
+    let mut graph: ~AdjacencyList<int> = MutableGraph::new(4);
+    for &(x, y) in [(0u,1u), (2,3), (3,1)].iter() {
+        assert!(graph.add(x, y, 1).is_ok());
+    }
+    assert_eq!(is_bipartite(&*graph).ok().unwrap(), vec!(Left, Right, Right, Left));
+    // Closing 0 - 1 - 3 - 0 makes an odd cycle.
+    assert!(graph.add(0, 3, 1).is_ok());
+    assert_eq!(is_bipartite(&*graph).err().unwrap(), vec!(1u, 0, 3));
+}
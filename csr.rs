@@ -0,0 +1,141 @@
+/*
+ * Christopher Piraino
+ *
+ * Compressed sparse row representation of
+ * a graph. It is built once from another
+ * graph and cannot be modified afterwards.
+ */
+use std::vec::Vec;
+use std::result::Result;
+use graph::{Graph, check_bounds};
+#[test]
+use graph::{Continue, MutableGraph, AdjacencyMatrix};
+#[test]
+use shortest_path::dijkstra;
+
+pub struct CsrGraph<W> {
+    // The edges of vertex v are stored at offsets[v]..offsets[v+1] in
+    // targets and weights, with targets sorted within each vertex.
+    offsets: Vec<uint>,
+    targets: Vec<uint>,
+    weights: Vec<W>
+}
+
+impl<W: Clone> CsrGraph<W> {
+    // Copies the edges of any graph into a new CSR layout.
+    pub fn from_graph<G: Graph<W>>(graph: &G) -> ~CsrGraph<W> {
+        let vertices = graph.vertices();
+        let mut offsets = Vec::with_capacity(vertices + 1);
+        let mut targets = Vec::new();
+        let mut weights = Vec::new();
+        offsets.push(0);
+        for x in range(0, vertices) {
+            let mut row: Vec<uint> = graph.neighbors(x);
+            row.as_mut_slice().sort();
+            for &y in row.iter() {
+                targets.push(y);
+                weights.push(graph.get_edge_value(x, y).unwrap().unwrap());
+            }
+            offsets.push(targets.len());
+        }
+        ~CsrGraph { offsets: offsets, targets: targets, weights: weights }
+    }
+    pub fn edges(&self) -> uint {
+        self.targets.len()
+    }
+    // The neighbors of x, without copying.
+    pub fn neighbor_slice<'a>(&'a self, x: uint) -> &'a [uint] {
+        self.targets.slice(*self.offsets.get(x), *self.offsets.get(x + 1))
+    }
+    // The values of the edges leaving x, in the same order as neighbor_slice.
+    pub fn weight_slice<'a>(&'a self, x: uint) -> &'a [W] {
+        self.weights.slice(*self.offsets.get(x), *self.offsets.get(x + 1))
+    }
+    fn check_bounds(&self, x: uint, y: uint) -> Result<(), ~str> {
+        check_bounds(self.vertices(), x, y)
+    }
+}
+
+impl<W: Clone> Graph<W> for CsrGraph<W> {
+    fn vertices(&self) -> uint {
+        self.offsets.len() - 1
+    }
+    fn adjacent(&self, x: uint, y: uint) -> Result<bool, ~str> {
+        try!(self.check_bounds(x, y));
+        Ok(self.neighbor_slice(x).bsearch_elem(&y).is_some())
+    }
+    // The Graph trait hands out an owned Vec, so this copies the neighbors
+    // of x on every call. Generic algorithms all go through it, which costs
+    // them most of the locality of the layout; code written against CsrGraph
+    // itself should use neighbor_slice and weight_slice instead.
+    fn neighbors(&self, x: uint) -> Vec<uint> {
+        Vec::from_slice(self.neighbor_slice(x))
+    }
+    fn get_edge_value(&self, x: uint, y: uint) -> Result<Option<W>, ~str> {
+        try!(self.check_bounds(x, y));
+        match self.neighbor_slice(x).bsearch_elem(&y) {
+            Some(i) => Ok(Some(self.weight_slice(x)[i].clone())),
+            None => Ok(None)
+        }
+    }
+}
+
+/*
+ *  Graph:
+ *  V 0 1 2 3 4
+ *  0 0 1 2 0 0
+ *  1 0 0 0 0 3
+ *  2 0 0 0 4 5
+ *  3 6 0 0 0 0
+ *  4 0 0 0 7 0
+ *
+ */
+#[cfg(test)]
+fn matrix_graph() -> ~AdjacencyMatrix<int> {
+    let mut graph: ~AdjacencyMatrix<int> = MutableGraph::new(5);
+    assert!(graph.add(0,1,1).is_ok());
+    assert!(graph.add(0,2,2).is_ok());
+    assert!(graph.add(1,4,3).is_ok());
+    assert!(graph.add(2,3,4).is_ok());
+    assert!(graph.add(2,4,5).is_ok());
+    assert!(graph.add(3,0,6).is_ok());
+    assert!(graph.add(4,3,7).is_ok());
+    graph
+}
+
+#[test]
+fn test_csr_from_graph() {
+    let matrix = matrix_graph();
+    let csr = CsrGraph::from_graph(&*matrix);
+    assert_eq!(csr.vertices(), 5);
+    assert_eq!(csr.edges(), 7);
+    assert_eq!(Vec::from_slice(csr.neighbor_slice(2)), vec!(3u, 4));
+    assert_eq!(Vec::from_slice(csr.weight_slice(2)), vec!(4, 5));
+    assert_eq!(Vec::from_slice(csr.neighbor_slice(3)), vec!(0u));
+    for x in range(0u, 5) {
+        assert_eq!(csr.neighbors(x), matrix.neighbors(x));
+        for y in range(0u, 5) {
+            assert_eq!(csr.adjacent(x, y).ok(), matrix.adjacent(x, y).ok());
+            assert_eq!(csr.get_edge_value(x, y).ok(), matrix.get_edge_value(x, y).ok());
+        }
+    }
+    assert!(csr.adjacent(5, 0).is_err());
+}
+
+#[test]
+fn test_csr_algorithms() {
+    let matrix = matrix_graph();
+    let csr = CsrGraph::from_graph(&*matrix);
+    let mut csr_dfs = Vec::new();
+    let mut matrix_dfs = Vec::new();
+    csr.depth_first_search(|_, v| { csr_dfs.push(v); Continue }, 0);
+    matrix.depth_first_search(|_, v| { matrix_dfs.push(v); Continue }, 0);
+    assert_eq!(csr_dfs, matrix_dfs);
+    let mut csr_bfs = Vec::new();
+    let mut matrix_bfs = Vec::new();
+    csr.breadth_first_search(|_, v| { csr_bfs.push(v); Continue }, 0);
+    matrix.breadth_first_search(|_, v| { matrix_bfs.push(v); Continue }, 0);
+    assert_eq!(csr_bfs, matrix_bfs);
+    let paths = dijkstra(&*csr, 0).ok().unwrap();
+    assert_eq!(paths.distance, dijkstra(&*matrix, 0).ok().unwrap().distance);
+}
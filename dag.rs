@@ -0,0 +1,104 @@
+/*
+ * Christopher Piraino
+ *
+ * Algorithms on directed acyclic graphs.
+ */
+use std::vec::Vec;
+use std::result::Result;
+use graph::{Graph, Control, Continue, Stop};
+use traversal::{DfsVisitor, depth_first_visit_all};
+#[test]
+use graph::{MutableGraph, AdjacencyMatrix, search_graph};
+
+// Records the finishing order and the DFS tree, stopping at the first back edge.
+struct TopologicalVisitor {
+    finished: Vec<uint>,
+    parent: Vec<Option<uint>>,
+    back_edge: Option<(uint, uint)>
+}
+
+impl<G> DfsVisitor<G> for TopologicalVisitor {
+    fn finish_vertex(&mut self, _: &G, v: uint, _: uint) -> Control {
+        self.finished.push(v);
+        Continue
+    }
+    fn tree_edge(&mut self, _: &G, u: uint, v: uint) -> Control {
+        *self.parent.get_mut(v) = Some(u);
+        Continue
+    }
+    fn back_edge(&mut self, _: &G, u: uint, v: uint) -> Control {
+        self.back_edge = Some((u, v));
+        Stop
+    }
+}
+
+/*  Orders the vertices so that every edge goes from an earlier vertex to a
+ *  later one, by reversing the order in which a depth first search finishes
+ *  them. If the graph has a cycle there is no such order, and the vertices of
+ *  a cycle are returned instead, in the order of its edges.
+ */
+pub fn topological_sort<W, G: Graph<W>>(graph: &G) -> Result<Vec<uint>, Vec<uint>> {
+    let vertices = graph.vertices();
+    let mut visitor = TopologicalVisitor {
+        finished: Vec::with_capacity(vertices),
+        parent: Vec::from_elem(vertices, None),
+        back_edge: None
+    };
+    depth_first_visit_all(graph, &mut visitor);
+    match visitor.back_edge {
+        // The tree path from v down to u closes a cycle with the edge (u, v).
+        Some((u, v)) => {
+            let mut cycle = vec!(u);
+            let mut current = u;
+            while current != v {
+                current = (*visitor.parent.get(current)).unwrap();
+                cycle.push(current);
+            }
+            cycle.reverse();
+            Err(cycle)
+        }
+        None => {
+            visitor.finished.reverse();
+            Ok(visitor.finished)
+        }
+    }
+}
+
+pub fn is_dag<W, G: Graph<W>>(graph: &G) -> bool {
+    topological_sort(graph).is_ok()
+}
+
+#[test]
+fn test_topological_sort() {
+    let mut graph: ~AdjacencyMatrix<int> = MutableGraph::new(6);
+    let edges = [(5u,2u), (5,0), (4,0), (4,1), (2,3), (3,1)];
+    for &(x, y) in edges.iter() {
+        assert!(graph.add(x, y, 1).is_ok());
+    }
+    assert!(is_dag(&*graph));
+    let order = topological_sort(&*graph).ok().unwrap();
+    assert_eq!(order.len(), 6);
+    let mut position = Vec::from_elem(6, 0u);
+    for (i, &v) in order.iter().enumerate() {
+        *position.get_mut(v) = i;
+    }
+    for &(x, y) in edges.iter() {
+        assert!(*position.get(x) < *position.get(y));
+    }
+}
+
+#[test]
+fn test_topological_sort_cycle() {
+    // The search test graph, which has the cycle 0 - 1 - 4 - 3 - 0.
+    let graph: ~AdjacencyMatrix<int> = search_graph();
+    assert!(!is_dag(&*graph));
+    let cycle = topological_sort(&*graph).err().unwrap();
+    assert_eq!(cycle, vec!(0u, 1, 4, 3));
+    for i in range(0, cycle.len()) {
+        let next = *cycle.get((i + 1) % cycle.len());
+        assert!(graph.adjacent(*cycle.get(i), next).ok().unwrap());
+    }
+    let mut looped: ~AdjacencyMatrix<int> = MutableGraph::new(2);
+    assert!(looped.add(1, 1, 1).is_ok());
+    assert_eq!(topological_sort(&*looped).err().unwrap(), vec!(1u));
+}
@@ -9,6 +9,7 @@ use collections::dlist::DList;
 use collections::deque::Deque;
 use std::option::Option;
 use std::cast;
+use std::mem::replace;
 
 pub type FibEntry<K,V> = *mut FibNode<K,V>;
 trait HeapEntry<K, V> {
@@ -62,16 +63,17 @@ impl<K: std::fmt::Show+Eq+Clone, V: Clone> HeapEntry<K,V> for FibEntry<K,V> {
 
 // Private Methods on Node values.
 impl<K: std::fmt::Show+Eq+Clone ,V: Clone> FibNode<K,V> {
-    fn remove_child(&mut self, key: K) {
+    // Children are matched by identity rather than key, since keys need not be unique.
+    fn remove_child(&mut self, child: FibEntry<K,V>) {
         for _ in range(0, self.children.len()) {
-            print!("Child key: {}\n", self.children.front().unwrap().key());
-            if self.children.front().unwrap().key().eq(&key) {
+            if *self.children.front().unwrap() == child {
                 self.children.pop_front();
+                unsafe { (*child).parent = None; }
                 return;
             }
             self.children.rotate_backward();
         }
-        fail!("Failed to remove child with key: {}\n", key);
+        fail!("Failed to remove child with key: {}\n", child.key());
     }
     fn rank(&self) -> uint {
         self.children.len()
@@ -80,32 +82,42 @@ impl<K: std::fmt::Show+Eq+Clone ,V: Clone> FibNode<K,V> {
 
 // Private methods on FHeap.
 impl<K: std::fmt::Show+Eq+Clone,V: Clone> FHeap<K,V> {
-    fn remove_child(&mut self, node: FibEntry<K,V>, key: K) {
-        print!("FHeap.remove_child({}, {})\n", node, key);
-        unsafe { (*node).remove_child(key); }
+    fn remove_child(&mut self, node: FibEntry<K,V>, child: FibEntry<K,V>) {
+        unsafe { (*node).remove_child(child); }
         if node.parent().is_some() {
             if node.mark() {
-                print!("start cascading cut\n");
                 self.cascading_cut(node);
             } else {
-                print!("first removed node, mark node\n");
                 unsafe { (*node).marked = true; }
             }
         }
     }
     fn cascading_cut(&mut self, node: FibEntry<K,V>) {
-        self.remove_child(node.parent().unwrap(), node.key());
+        self.remove_child(node.parent().unwrap(), node);
         self.trees.push_back(node)
     }
+    // Unlinks a root from the list of trees, keeping the remaining roots
+    // in their original order so the minimum stays at the front.
+    fn remove_root(&mut self, node: FibEntry<K,V>) -> bool {
+        for i in range(0, self.trees.len()) {
+            if *self.trees.front().unwrap() == node {
+                self.trees.pop_front();
+                for _ in range(0, i) {
+                    self.trees.rotate_forward();
+                }
+                return true;
+            }
+            self.trees.rotate_backward();
+        }
+        false
+    }
     fn same_rank(&mut self) -> Option<(FibEntry<K,V>, FibEntry<K,V>)> {
         // Only a single tree, no linking step.
         if self.trees.len() == 1 {
             return None;
         }
-        print!("Looking for roots of same rank\n");
         let mut same = false;
-        for i in range(0, self.trees.len()) {
-            print!("On {}th iteration of self.trees loop\n", i);
+        for _ in range(0, self.trees.len()) {
             {
                 let front = self.trees.front().unwrap();
                 let back = self.trees.back().unwrap();
@@ -118,7 +130,6 @@ impl<K: std::fmt::Show+Eq+Clone,V: Clone> FHeap<K,V> {
                              self.trees.pop_back().unwrap()));
             }
             self.trees.rotate_backward();
-            print!("End of {}th iteration\n", i);
         }
         return None;
     }
@@ -155,6 +166,9 @@ impl<K: Clone + Sub<K,K> + Ord + std::fmt::Show, V: Clone> FHeap<K, V> {
         self.meld(singleton);
         ret
     }
+    pub fn is_empty(&self) -> bool {
+        self.trees.is_empty()
+    }
     // Returns a copy of the minimum key and value.
     pub fn find_min(& self) -> (K, V) {
         match self.trees.front() {
@@ -168,28 +182,28 @@ impl<K: Clone + Sub<K,K> + Ord + std::fmt::Show, V: Clone> FHeap<K, V> {
         let min_tree = self.trees.pop_front().unwrap();
         let value = min_tree.value();
         let key = min_tree.key();
-        print!("Setting parent of children to None\n");
         unsafe {
             for n in (*min_tree).children.mut_iter() {
                 (**n).parent = None;
             }
-            print!("Appending children to root list\n");
             self.trees.append((*min_tree).children.clone());
         }
+        // Removed the last node, nothing left to consolidate.
+        if self.trees.is_empty() {
+            unsafe { drop(cast::transmute::<_, ~FibNode<K,V>>(min_tree)); }
+            return (key, value)
+        }
         //let mut dlist = DList::new();
         // Explicit closure scope.
         {
             // Closure to find to trees with the same rank.
             let mut link = self.same_rank();
             while link.is_some() {
-                print!("merging roots of same rank\n");
                 let (a, b) = link.unwrap();
                 if a.key().lt(&b.key()) {
-                    print!("less: {} more: {}\n", a.key(), b.key());
                     a.link(b);
                     self.trees.push_front(a);
                 } else {
-                    print!("less: {} more: {}\n", b.key(), a.key());
                     b.link(a);
                     self.trees.push_front(b);
                 }
@@ -199,11 +213,8 @@ impl<K: Clone + Sub<K,K> + Ord + std::fmt::Show, V: Clone> FHeap<K, V> {
         // Append all newly formed roots to list of roots.
         //self.trees.append(dlist);
         // Find the minimum node and put the tree first.
-        print!("Finding minimum node and putting at front\n");
         let mut min_node = self.trees.pop_front().unwrap();
-        print!("min node: {}, self.trees.len(): {}\n", min_node.key(), self.trees.len());
         for _ in range(0, self.trees.len()) {
-            print!("min_node: {} tree.front: {}\n", min_node.key(), self.trees.front().unwrap().key());
             if self.trees.front().unwrap().key().lt(&min_node.key()) {
                 self.trees.push_back(min_node);
                 min_node = self.trees.pop_front().unwrap();
@@ -216,25 +227,29 @@ impl<K: Clone + Sub<K,K> + Ord + std::fmt::Show, V: Clone> FHeap<K, V> {
         unsafe { drop(cast::transmute::<_, ~FibNode<K,V>>(min_tree)); }
         (key, value)
     }
-    pub fn meld(&mut self, other: FHeap<K, V>) {
+    pub fn meld(&mut self, mut other: FHeap<K, V>) {
+        // The trees are taken rather than moved out, since other is dropped afterwards.
+        let trees = replace(&mut other.trees, DList::new());
         if self.trees.is_empty() {
-            self.trees.append(other.trees);
-        } else if self.find_min().val0() <= other.find_min().val0() {
-            self.trees.append(other.trees);
+            self.trees.append(trees);
+        } else if self.find_min().val0() <= trees.front().unwrap().key() {
+            self.trees.append(trees);
         } else {
-            self.trees.prepend(other.trees);
+            self.trees.prepend(trees);
         }
     }
     pub fn decrease_key(&mut self, node: FibEntry<K,V>, delta: K) {
         unsafe { (*node).key = (*node).key - delta; }
         if node.parent().is_none() {
-            print!("node: {} has no parent\n", node.key());
+            // A root that drops below the minimum becomes the front tree.
+            if node.key().lt(&self.find_min().val0()) {
+                self.remove_root(node);
+                self.trees.push_front(node);
+            }
             return
         }
-        print!("parent is Some\n");
-        print!("parent: {}\n", node.parent());
         let parent = node.parent().unwrap();
-        self.remove_child(parent, node.key());
+        self.remove_child(parent, node);
         if self.find_min().val0().lt(&node.key()) {
             self.trees.push_back(node.clone());
         } else {
@@ -242,19 +257,16 @@ impl<K: Clone + Sub<K,K> + Ord + std::fmt::Show, V: Clone> FHeap<K, V> {
         }
     }
     pub fn delete(&mut self, node: FibEntry<K,V>) -> (K, V) {
-        if self.find_min().val0() == node.key() {
+        if *self.trees.front().unwrap() == node {
             return self.delete_min()
         } else if node.parent().is_none() {
             let key = node.key();
-            for _ in range(0, self.trees.len()) {
-                if self.trees.front().unwrap().key().eq(&key) {
-                    self.trees.pop_front();
-                    break;
-                }
-                self.trees.rotate_backward();
-            }
+            self.remove_root(node);
             let value = node.value();
             unsafe {
+                for n in (*node).children.mut_iter() {
+                    (**n).parent = None;
+                }
                 self.trees.append((*node).children.clone());
                 drop(cast::transmute::<_, ~FibNode<K,V>>(node));
             }
@@ -262,8 +274,11 @@ impl<K: Clone + Sub<K,K> + Ord + std::fmt::Show, V: Clone> FHeap<K, V> {
         } else {
             let key = node.key();
             let value = node.value();
-            self.remove_child(node.parent().unwrap(), node.key());
+            self.remove_child(node.parent().unwrap(), node);
             unsafe {
+                for n in (*node).children.mut_iter() {
+                    (**n).parent = None;
+                }
                 self.trees.append((*node).children.clone());
                 drop(cast::transmute::<_, ~FibNode<K,V>>(node));
             }
@@ -272,6 +287,25 @@ impl<K: Clone + Sub<K,K> + Ord + std::fmt::Show, V: Clone> FHeap<K, V> {
     }
 }
 
+/*  Frees the nodes still in the heap, which would otherwise leak whenever a
+ *  heap is dropped before it is emptied, such as by a search that stops once
+ *  it reaches its goal. The children of each freed node become roots so that
+ *  they are freed in turn.
+ */
+#[unsafe_destructor]
+impl<K, V> Drop for FHeap<K, V> {
+    fn drop(&mut self) {
+        loop {
+            match self.trees.pop_front() {
+                Some(ptr) => {
+                    let mut node: ~FibNode<K,V> = unsafe { cast::transmute(ptr) };
+                    self.trees.append(replace(&mut node.children, DList::new()));
+                }
+                None => break
+            }
+        }
+    }
+}
 
 /* 
  *
@@ -412,3 +446,56 @@ fn test_fheap_cascading_cut() {
     fheap.decrease_key(h3, 3);
     assert_eq!(fheap.trees.len(), 6);
 }
+
+#[test]
+fn test_fheap_delete_min_until_empty() {
+    let mut fheap = FHeap::new();
+    fheap.insert(2, "2");
+    fheap.insert(1, "1");
+    fheap.insert(3, "3");
+    assert_eq!(fheap.delete_min(), (1, "1"));
+    assert_eq!(fheap.delete_min(), (2, "2"));
+    assert_eq!(fheap.delete_min(), (3, "3"));
+    assert!(fheap.is_empty());
+}
+
+#[test]
+fn test_fheap_decrease_key_root_below_min() {
+    let mut fheap = FHeap::new();
+    fheap.insert(1, "1");
+    fheap.insert(3, "3");
+    let five = fheap.insert(5, "5");
+    fheap.decrease_key(five, 5);
+    assert_eq!(fheap.find_min(), (0, "5"));
+    assert_eq!(fheap.trees.len(), 3);
+}
+
+#[test]
+fn test_fheap_duplicate_keys() {
+    let mut fheap = FHeap::new();
+    fheap.insert(0, "a");
+    let b = fheap.insert(4, "b");
+    let c = fheap.insert(4, "c");
+    fheap.insert(4, "d");
+    fheap.insert(4, "e");
+    fheap.delete_min();
+    fheap.decrease_key(c, 3);
+    fheap.decrease_key(b, 1);
+    assert_eq!(fheap.delete_min(), (1, "c"));
+    assert_eq!(fheap.delete_min(), (3, "b"));
+    assert_eq!(fheap.delete_min().val0(), 4);
+    assert_eq!(fheap.delete_min().val0(), 4);
+    assert!(fheap.is_empty());
+}
+
+#[test]
+fn test_fheap_drop_nonempty() {
+    // Consolidation leaves trees with children, which must all be freed with the heap.
+    let mut fheap = FHeap::new();
+    for i in range(0, 9) {
+        fheap.insert(i, format!("{}", i));
+    }
+    fheap.delete_min();
+    assert_eq!(fheap.find_min(), (1, ~"1"));
+    drop(fheap);
+}
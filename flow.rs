@@ -0,0 +1,273 @@
+/*
+ * Christopher Piraino
+ *
+ * Maximum flow between two vertices, with
+ * edge values taken as capacities.
+ */
+extern crate collections = "collections#0.11-pre";
+use collections::ringbuf::RingBuf;
+use collections::deque::Deque;
+use std::vec::Vec;
+use std::result::Result;
+use std::num::Zero;
+use graph::{Graph, MutableGraph, Weight, check_bounds};
+#[test]
+use graph::AdjacencyMatrix;
+#[test]
+use adjacency_list::AdjacencyList;
+
+/*  A maximum flow along with a minimum cut. The cut is the set of vertices
+ *  still reachable from the source once no more flow can be pushed. Every edge
+ *  leaving it is saturated and every edge entering it is empty, so the
+ *  capacities across it add up to the value of the flow.
+ */
+pub struct MaxFlow<W, H> {
+    pub value: W,
+    // The flow along each edge, leaving out the edges that carry none.
+    pub flow: ~H,
+    // The source side of the cut, in increasing order.
+    pub cut: Vec<uint>
+}
+
+/*  The residual network as a list of edges. Edge 2k is the kth edge of the
+ *  graph and edge 2k + 1 its reverse, which starts with no capacity, so
+ *  pushing flow along one edge of a pair gives the same capacity back to the
+ *  other and the tail of edge e is the head of edge e ^ 1.
+ */
+struct Residual<W> {
+    head: Vec<uint>,
+    capacity: Vec<W>,
+    // The residual edges leaving each vertex.
+    out: Vec<Vec<uint>>
+}
+
+impl<W: Weight> Residual<W> {
+    fn new<G: Graph<W>>(graph: &G, source: uint, sink: uint) -> Result<Residual<W>, ~str> {
+        let vertices = graph.vertices();
+        try!(check_bounds(vertices, source, sink));
+        if source == sink {
+            return Err(format!("The source and sink are both {}.", source));
+        }
+        let zero: W = Zero::zero();
+        let mut residual = Residual {
+            head: Vec::new(),
+            capacity: Vec::new(),
+            out: Vec::from_fn(vertices, |_| Vec::new())
+        };
+        for u in range(0, vertices) {
+            for &v in graph.neighbors(u).iter() {
+                // A self loop can never carry flow anywhere.
+                if u == v {
+                    continue;
+                }
+                let capacity = try!(graph.get_edge_value(u, v)).unwrap();
+                if capacity < zero {
+                    return Err(format!("({}, {}) has negative capacity {}.", u, v, capacity));
+                }
+                let e = residual.head.len();
+                residual.head.push(v);
+                residual.head.push(u);
+                residual.capacity.push(capacity);
+                residual.capacity.push(zero.clone());
+                residual.out.get_mut(u).push(e);
+                residual.out.get_mut(v).push(e + 1);
+            }
+        }
+        Ok(residual)
+    }
+
+    /*  A breadth first search from the source along the edges with capacity
+     *  left. Gives the distance of each vertex reached, and the edge that
+     *  reached it first.
+     */
+    fn search(&self, source: uint) -> (Vec<Option<uint>>, Vec<Option<uint>>) {
+        let zero: W = Zero::zero();
+        let mut level: Vec<Option<uint>> = Vec::from_elem(self.out.len(), None);
+        let mut via: Vec<Option<uint>> = Vec::from_elem(self.out.len(), None);
+        let mut queue = RingBuf::new();
+        *level.get_mut(source) = Some(0u);
+        queue.push_back(source);
+        while !queue.is_empty() {
+            let u = queue.pop_front().unwrap();
+            let depth = (*level.get(u)).unwrap();
+            for &e in self.out.get(u).iter() {
+                let v = *self.head.get(e);
+                if level.get(v).is_none() && *self.capacity.get(e) > zero {
+                    *level.get_mut(v) = Some(depth + 1);
+                    *via.get_mut(v) = Some(e);
+                    queue.push_back(v);
+                }
+            }
+        }
+        (level, via)
+    }
+
+    // Pushes the largest flow the edges of a path allow along it, returning how much.
+    fn augment(&mut self, path: &Vec<uint>) -> W {
+        let mut bottleneck = self.capacity.get(*path.get(0)).clone();
+        for &e in path.iter() {
+            if *self.capacity.get(e) < bottleneck {
+                bottleneck = self.capacity.get(e).clone();
+            }
+        }
+        for &e in path.iter() {
+            let lowered = *self.capacity.get(e) - bottleneck;
+            *self.capacity.get_mut(e) = lowered;
+            let raised = *self.capacity.get(e ^ 1) + bottleneck;
+            *self.capacity.get_mut(e ^ 1) = raised;
+        }
+        bottleneck
+    }
+
+    /*  Reads the flow off the reverse edges, which hold exactly what has been
+     *  pushed along the edges they pair with, and the cut off the levels of
+     *  the final search, which failed to reach the sink.
+     */
+    fn finish<H: MutableGraph<W>>(&self, value: W, level: &Vec<Option<uint>>) -> MaxFlow<W, H> {
+        let zero: W = Zero::zero();
+        let mut flow: ~H = MutableGraph::new(self.out.len());
+        for e in range(0, self.head.len() / 2).map(|k| 2 * k) {
+            let pushed = self.capacity.get(e + 1);
+            if *pushed > zero {
+                flow.add(*self.head.get(e + 1), *self.head.get(e), pushed.clone()).unwrap();
+            }
+        }
+        let cut = range(0, level.len()).filter(|&v| level.get(v).is_some()).collect();
+        MaxFlow { value: value, flow: flow, cut: cut }
+    }
+}
+
+/*  Edmonds-Karp, which augments along a shortest path with capacity left
+ *  until there is none, taking O(V E^2) time.
+ */
+pub fn edmonds_karp<W: Weight, G: Graph<W>, H: MutableGraph<W>>(graph: &G, source: uint, sink: uint)
+                                                                -> Result<MaxFlow<W, H>, ~str> {
+    let mut residual = try!(Residual::new(graph, source, sink));
+    let mut value: W = Zero::zero();
+    loop {
+        let (level, via) = residual.search(source);
+        if level.get(sink).is_none() {
+            return Ok(residual.finish(value, &level));
+        }
+        let mut path = Vec::new();
+        let mut v = sink;
+        while v != source {
+            let e = (*via.get(v)).unwrap();
+            path.push(e);
+            v = *residual.head.get(e ^ 1);
+        }
+        value = value + residual.augment(&path);
+    }
+}
+
+/*  Dinic's algorithm, taking O(V^2 E) time. Each phase levels the residual
+ *  network with a breadth first search, then pushes a blocking flow along the
+ *  edges that go from one level to the next. A depth first search walks
+ *  forward from the source, and whenever it reaches the sink the flow is
+ *  pushed and the search starts over. Each vertex keeps its place in its
+ *  list of edges so that no edge is tried twice in a phase, and a vertex
+ *  found to be a dead end is dropped from the levels.
+ */
+pub fn dinic<W: Weight, G: Graph<W>, H: MutableGraph<W>>(graph: &G, source: uint, sink: uint)
+                                                         -> Result<MaxFlow<W, H>, ~str> {
+    let mut residual = try!(Residual::new(graph, source, sink));
+    let zero: W = Zero::zero();
+    let mut value: W = Zero::zero();
+    loop {
+        let (mut level, _) = residual.search(source);
+        if level.get(sink).is_none() {
+            return Ok(residual.finish(value, &level));
+        }
+        let mut position = Vec::from_elem(residual.out.len(), 0u);
+        loop {
+            let mut path: Vec<uint> = Vec::new();
+            let mut u = source;
+            while u != sink {
+                let i = *position.get(u);
+                if i == residual.out.get(u).len() {
+                    if u == source {
+                        break;
+                    }
+                    *level.get_mut(u) = None;
+                    u = *residual.head.get(path.pop().unwrap() ^ 1);
+                    continue;
+                }
+                let e = *residual.out.get(u).get(i);
+                let v = *residual.head.get(e);
+                if *residual.capacity.get(e) > zero &&
+                   *level.get(v) == (*level.get(u)).map(|d| d + 1) {
+                    path.push(e);
+                    u = v;
+                } else {
+                    *position.get_mut(u) = i + 1;
+                }
+            }
+            if u != sink {
+                break;
+            }
+            value = value + residual.augment(&path);
+        }
+    }
+}
+
+#[cfg(test)]
+fn capacities<W: Clone>(vertices: uint, edges: &[(uint, uint, W)]) -> ~AdjacencyList<W> {
+    let mut graph: ~AdjacencyList<W> = MutableGraph::new(vertices);
+    for &(x, y, ref val) in edges.iter() {
+        assert!(graph.add(x, y, val.clone()).is_ok());
+    }
+    graph
+}
+
+/*
+ *  0 -> 1 (16), 0 -> 2 (13)
+ *  1 -> 3 (12)
+ *  2 -> 1 (4), 2 -> 4 (14)
+ *  3 -> 2 (9), 3 -> 5 (20)
+ *  4 -> 3 (7), 4 -> 5 (4)
+ *
+ */
+#[test]
+fn test_max_flow() {
+    let graph = capacities(6, &[(0,1,16), (0,2,13), (1,3,12), (2,1,4), (2,4,14),
+                                (3,2,9), (3,5,20), (4,3,7), (4,5,4)]);
+    let by_edmonds_karp: MaxFlow<int, AdjacencyMatrix<int>> = edmonds_karp(&*graph, 0, 5).ok().unwrap();
+    let by_dinic: MaxFlow<int, AdjacencyMatrix<int>> = dinic(&*graph, 0, 5).ok().unwrap();
+    for result in [by_edmonds_karp, by_dinic].iter() {
+        assert_eq!(result.value, 23);
+        assert_eq!(result.cut, vec!(0u, 1, 2, 4));
+        // Each edge stays within its capacity, and flow is conserved everywhere
+        // except at the source and sink.
+        let mut balance = Vec::from_elem(6, 0);
+        for u in range(0u, 6) {
+            for &v in result.flow.neighbors(u).iter() {
+                let pushed = result.flow.get_edge_value(u, v).ok().unwrap().unwrap();
+                assert!(pushed <= graph.get_edge_value(u, v).ok().unwrap().unwrap());
+                *balance.get_mut(u) -= pushed;
+                *balance.get_mut(v) += pushed;
+            }
+        }
+        assert_eq!(balance, vec!(-23, 0, 0, 0, 0, 23));
+    }
+}
+
+#[test]
+fn test_max_flow_unreachable_and_invalid() {
+    let graph = capacities(4, &[(0u,1u,3.5), (1,0,2.0), (1,2,1.5), (0,2,1.0)]);
+    let result: MaxFlow<f64, AdjacencyMatrix<f64>> = dinic(&*graph, 0, 2).ok().unwrap();
+    assert_eq!(result.value, 2.5);
+    assert_eq!(result.cut, vec!(0u, 1));
+    assert_eq!(result.flow.get_edge_value(0, 1).ok().unwrap(), Some(1.5));
+    assert_eq!(result.flow.get_edge_value(1, 0).ok().unwrap(), None);
+    let result: MaxFlow<f64, AdjacencyMatrix<f64>> = edmonds_karp(&*graph, 0, 3).ok().unwrap();
+    assert_eq!(result.value, 0.0);
+    assert_eq!(result.cut, vec!(0u, 1, 2));
+    assert!(result.flow.neighbors(0).is_empty());
+    let invalid: Result<MaxFlow<f64, AdjacencyMatrix<f64>>, ~str> = dinic(&*graph, 1, 1);
+    assert!(invalid.is_err());
+    let invalid: Result<MaxFlow<f64, AdjacencyMatrix<f64>>, ~str> = edmonds_karp(&*graph, 0, 4);
+    assert!(invalid.is_err());
+    let negative = capacities(2, &[(0u,1u,-1)]);
+    let invalid: Result<MaxFlow<int, AdjacencyMatrix<int>>, ~str> = dinic(&*negative, 0, 1);
+    assert!(invalid.is_err());
+}
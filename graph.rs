@@ -6,56 +6,250 @@
  *  structures.
  *
  */
+extern crate collections = "collections#0.11-pre";
+use collections::ringbuf::RingBuf;
+use collections::deque::Deque;
 use std::vec::Vec;
 use std::result::Result;
+use std::default::Default;
+use std::fmt::Show;
+use std::num::Zero;
 #[test]
 use std::io::stdio;
 
-pub trait Graph {
-    fn new(vertices: uint) -> ~Self;
+/*  What the weighted algorithms need from an edge value: a zero, addition and
+ *  subtraction, and an ordering. Integers and floats are weights as they are,
+ *  and a custom cost struct becomes one by implementing these traits.
+ */
+pub trait Weight: Clone + Ord + Add<Self, Self> + Sub<Self, Self> + Zero + Show {}
+
+impl<W: Clone + Ord + Add<W, W> + Sub<W, W> + Zero + Show> Weight for W {}
+
+// Whether candidate is smaller than the best value known so far, if any.
+pub fn improves<W: Weight>(candidate: &W, known: &Option<W>) -> bool {
+    match *known {
+        Some(ref old) => *candidate < *old,
+        None => true
+    }
+}
+
+// Returned by the closures passed to the searches to steer them.
+#[deriving(Eq, Show)]
+pub enum Control {
+    Continue, // Keep searching.
+    Prune,    // Do not search past this vertex.
+    Stop      // End the search at this vertex.
+}
+
+/*  The read-only operations on a graph with edge values of type W, which is
+ *  all the algorithms need. An edge that is not there has no value, so
+ *  get_edge_value returns None for it.
+ */
+pub trait Graph<W> {
+    fn vertices(&self) -> uint;
     fn adjacent(&self, x: uint, y: uint) -> Result<bool, ~str>;
     fn neighbors(&self, x: uint) -> Vec<uint>;
-    fn add(&mut self, x: uint, y: uint, val: int) -> Result<int, ~str>;
-    fn delete(&mut self, x: uint, y: uint) -> Result<int, ~str>;
-    fn get_edge_value(&self, x: uint, y: uint) -> Result<int, ~str>;
-    fn set_edge_value(&mut self, x: uint, y: uint, val: int) -> Result<int, ~str>;
-    fn depth_first_search(&self, closure: |graph: &Self, v: uint|, start: uint);
-    fn breadth_first_search(&self, closure: |graph: &Self, v: uint|, start: uint);
+    fn get_edge_value(&self, x: uint, y: uint) -> Result<Option<W>, ~str>;
+    // Calls the closure on the vertices in DFS order, passing in the graph as well.
+    // Returns the vertex the closure stopped the search at, if any.
+    fn depth_first_search(&self, closure: |graph: &Self, v: uint| -> Control,
+                          start: uint) -> Option<uint> {
+        let mut visited = Vec::from_fn(self.vertices(), |_| 0);
+        let mut stack = Vec::new();
+        stack.push(start);
+        // Continue looping until all vertices are visited.
+        while stack.len() != 0 {
+            let current = stack.pop().unwrap();
+            if *visited.get(current) == 1 {
+                continue;
+            }
+            *visited.get_mut(current) = 1;
+            match closure(self, current) {
+                Continue => {}
+                Prune => continue,
+                Stop => return Some(current)
+            }
+            for x in self.neighbors(current).iter() {
+                stack.push(*x);
+            }
+        }
+        None
+    }
+    fn breadth_first_search(&self, closure: |graph: &Self, v: uint| -> Control,
+                            start: uint) -> Option<uint> {
+        let mut visited = Vec::from_fn(self.vertices(), |_| 0);
+        let mut queue = RingBuf::new();
+        queue.push_back(start);
+        // Continue looping until all vertices are visited.
+        while !queue.is_empty() {
+            let current = queue.pop_front().unwrap();
+            if *visited.get(current) == 1 {
+                continue;
+            }
+            *visited.get_mut(current) = 1;
+            match closure(self, current) {
+                Continue => {}
+                Prune => continue,
+                Stop => return Some(current)
+            }
+            for x in self.neighbors(current).iter() {
+                queue.push_back(*x);
+            }
+        }
+        None
+    }
+    // Lazily yields the vertices in the same order as depth_first_search.
+    fn dfs_iter<'a>(&'a self, start: uint) -> DfsIter<'a, W, Self> {
+        DfsIter {
+            graph: self,
+            visited: Vec::from_elem(self.vertices(), false),
+            stack: vec!(start)
+        }
+    }
+    // Lazily yields (vertex, depth, parent) in the same order as breadth_first_search.
+    fn bfs_iter<'a>(&'a self, start: uint) -> BfsIter<'a, W, Self> {
+        self.bfs_iter_from(&[start])
+    }
+    // Like bfs_iter, but every start is at depth 0, so each vertex gets its
+    // depth from the nearest of them. Repeated starts are only visited once.
+    fn bfs_iter_from<'a>(&'a self, starts: &[uint]) -> BfsIter<'a, W, Self> {
+        let mut visited = Vec::from_elem(self.vertices(), false);
+        let mut queue = RingBuf::new();
+        for &start in starts.iter() {
+            if !*visited.get(start) {
+                *visited.get_mut(start) = true;
+                queue.push_back((start, 0, None));
+            }
+        }
+        BfsIter { graph: self, visited: visited, queue: queue }
+    }
+}
+
+pub struct DfsIter<'a, W, G> {
+    graph: &'a G,
+    visited: Vec<bool>,
+    stack: Vec<uint>
+}
+
+impl<'a, W, G: Graph<W>> Iterator<uint> for DfsIter<'a, W, G> {
+    fn next(&mut self) -> Option<uint> {
+        loop {
+            let current = match self.stack.pop() {
+                Some(v) => v,
+                None => return None
+            };
+            if *self.visited.get(current) {
+                continue;
+            }
+            *self.visited.get_mut(current) = true;
+            for &x in self.graph.neighbors(current).iter() {
+                self.stack.push(x);
+            }
+            return Some(current);
+        }
+    }
+}
+
+// Vertices are marked when they are queued, so each is reached from the
+// first vertex to find it and has its shortest depth from the start.
+pub struct BfsIter<'a, W, G> {
+    graph: &'a G,
+    visited: Vec<bool>,
+    queue: RingBuf<(uint, uint, Option<uint>)>
+}
+
+impl<'a, W, G: Graph<W>> Iterator<(uint, uint, Option<uint>)> for BfsIter<'a, W, G> {
+    fn next(&mut self) -> Option<(uint, uint, Option<uint>)> {
+        let (current, depth, parent) = match self.queue.pop_front() {
+            Some(step) => step,
+            None => return None
+        };
+        for &x in self.graph.neighbors(current).iter() {
+            if !*self.visited.get(x) {
+                *self.visited.get_mut(x) = true;
+                self.queue.push_back((x, depth + 1, Some(current)));
+            }
+        }
+        Some((current, depth, parent))
+    }
+}
+
+/*  add inserts an edge or replaces its value, delete removes an edge and
+ *  returns the value it had, if any, and set_edge_value changes the value of
+ *  an edge that is already there.
+ */
+pub trait MutableGraph<W>: Graph<W> {
+    fn new(vertices: uint) -> ~Self;
+    fn add(&mut self, x: uint, y: uint, val: W) -> Result<W, ~str>;
+    fn delete(&mut self, x: uint, y: uint) -> Result<Option<W>, ~str>;
+    fn set_edge_value(&mut self, x: uint, y: uint, val: W) -> Result<W, ~str>;
+}
+
+/*  A graph whose vertices can be added and removed after it is created.
+ *  Indices are kept dense: a new vertex always gets the next index, and
+ *  removing a vertex moves every later vertex down by one. remove_vertex
+ *  returns the new index of every old vertex, with None for the removed one,
+ *  so that indices held elsewhere can be updated.
+ */
+pub trait DynamicGraph<W>: MutableGraph<W> {
+    fn add_vertex(&mut self) -> uint;
+    fn remove_vertex(&mut self, v: uint) -> Result<Vec<Option<uint>>, ~str>;
 }
 
-pub trait Matrix {
+// Checks that x and y are both vertices of a graph with the given number of vertices.
+pub fn check_bounds(vertices: uint, x: uint, y: uint) -> Result<(), ~str> {
+    if x >= vertices || y >= vertices {
+        Err(format!("({}, {}) is out of bounds, graph has {} vertices.", x, y, vertices))
+    } else {
+        Ok(())
+    }
+}
+
+// The table returned by remove_vertex when v is removed from a graph with
+// the given number of vertices.
+pub fn removal_remap(vertices: uint, v: uint) -> Vec<Option<uint>> {
+    Vec::from_fn(vertices, |u| {
+        if u < v { Some(u) } else if u == v { None } else { Some(u - 1) }
+    })
+}
+
+// A new matrix is filled with the default value of T, which is 0 for numbers.
+pub trait Matrix<T> {
     fn zero(row: uint, col: uint) -> ~Self;
-    fn height(&self) -> uint; 
+    fn height(&self) -> uint;
     fn width(&self) -> uint;
-    fn at(&self, row: uint, col: uint) -> Result<int, ~str>;
-    fn set(&mut self, row: uint, col: uint, val: int) -> Result<int, ~str>;
+    fn at(&self, row: uint, col: uint) -> Result<T, ~str>;
+    fn set(&mut self, row: uint, col: uint, val: T) -> Result<T, ~str>;
 }
 
-pub struct VectorMatrix {
-    elements: Vec<int>, // Elements are stored in row-major order.
+pub struct VectorMatrix<T> {
+    elements: Vec<T>, // Elements are stored in row-major order.
     height: uint,
     width: uint
 }
 
-impl Matrix for VectorMatrix {
-    fn zero(row: uint, col: uint) -> ~VectorMatrix {
-        let elem = Vec::from_fn(row*col, |_: uint| -> int { 0 });
+// An adjacency matrix, where None marks a missing edge.
+pub type AdjacencyMatrix<W> = VectorMatrix<Option<W>>;
+
+impl<T: Clone + Default> Matrix<T> for VectorMatrix<T> {
+    fn zero(row: uint, col: uint) -> ~VectorMatrix<T> {
+        let elem = Vec::from_fn(row*col, |_: uint| -> T { Default::default() });
         ~VectorMatrix { elements: elem, height: row, width: col }
     }
-    fn at(&self, row: uint, col: uint) -> Result<int, ~str> {
+    fn at(&self, row: uint, col: uint) -> Result<T, ~str> {
         if row >= self.height || col >= self.width {
             Err(format!("({}, {}) is out of bounds, height of matrix is {} and width of matrix is {}.",
                     row, col, self.height, self.width))
         } else {
-            Ok(*self.elements.get(row*self.width + col))
+            Ok(self.elements.get(row*self.width + col).clone())
         }
     }
-    fn set(& mut self, row: uint, col: uint, val: int) -> Result<int, ~str> {
+    fn set(& mut self, row: uint, col: uint, val: T) -> Result<T, ~str> {
         if row >= self.height || col >= self.width {
             Err(format!("({}, {}) is out of bounds, height of matrix is {} and width of matrix is {}.",
                     row, col, self.height, self.width))
         } else {
-            *self.elements.get_mut(row*self.width+col) = val;
+            *self.elements.get_mut(row*self.width+col) = val.clone();
             Ok(val)
         }
     }
@@ -63,14 +257,14 @@ impl Matrix for VectorMatrix {
     fn width(&self) -> uint {self.width}
 }
 
-impl Graph for VectorMatrix {
-    fn new(vertices: uint) -> ~VectorMatrix {
-        Matrix::zero(vertices, vertices)
+impl<W: Clone> Graph<W> for VectorMatrix<Option<W>> {
+    fn vertices(&self) -> uint {
+        self.width
     }
     fn adjacent(&self, x: uint, y: uint) -> Result<bool, ~str> {
         let res = self.at(x, y);
         match res {
-            Ok(n) => if n == 0 { Ok(false) } else { Ok(true) },
+            Ok(n) => Ok(n.is_some()),
             Err(err) => Err(err)
         }
     }
@@ -79,65 +273,74 @@ impl Graph for VectorMatrix {
         let mut adj = Vec::new();
         for r in row.iter().enumerate() {
             let (i, val) = r;
-            if *val != 0 {
+            if val.is_some() {
                 adj.push(i)
             }
         }
         return adj
     }
-    fn add(&mut self, x: uint, y: uint, val: int) -> Result<int, ~str> {
-        self.set(x, y, val)
+    fn get_edge_value(&self, x: uint, y: uint) -> Result<Option<W>, ~str> {
+        self.at(x, y)
     }
-    fn delete(&mut self, x: uint, y: uint) -> Result<int, ~str> {
-        self.set(x, y, 0)
+}
+
+impl<W: Clone> MutableGraph<W> for VectorMatrix<Option<W>> {
+    fn new(vertices: uint) -> ~VectorMatrix<Option<W>> {
+        Matrix::zero(vertices, vertices)
     }
-    fn get_edge_value(&self, x: uint, y: uint) -> Result<int, ~str> {
-        self.at(x, y)
+    fn add(&mut self, x: uint, y: uint, val: W) -> Result<W, ~str> {
+        try!(self.set(x, y, Some(val.clone())));
+        Ok(val)
     }
-    fn set_edge_value(&mut self, x: uint, y: uint, val: int) -> Result<int, ~str> {
-        // TODO: If x, y are not adjacent, return Err().
-        self.set(x, y, val)
+    fn delete(&mut self, x: uint, y: uint) -> Result<Option<W>, ~str> {
+        let old = try!(self.at(x, y));
+        try!(self.set(x, y, None));
+        Ok(old)
     }
-    // Calls the closure on the vertices in DFS order, passing in the graph as well.
-    fn depth_first_search(&self, closure: |graph: &VectorMatrix, v: uint|, start: uint) {
-        let mut visited = Vec::from_fn(self.width(), |_| 0);
-        let mut stack = Vec::new();
-        stack.push(start);
-        // Continue looping until all vertices are visited.
-        while stack.len() != 0 {
-            let current = stack.pop().unwrap();
-            if *visited.get(current) == 1 {
-                continue;
-            }
-            closure(self, current);
-            *visited.get_mut(current) = 1;
-            for x in self.neighbors(current).iter() {
-                stack.push(*x);
-            }
+    fn set_edge_value(&mut self, x: uint, y: uint, val: W) -> Result<W, ~str> {
+        if !try!(self.adjacent(x, y)) {
+            return Err(format!("There is no edge from {} to {}.", x, y));
         }
+        self.add(x, y, val)
     }
-    fn breadth_first_search(&self, closure: |graph: &VectorMatrix, v: uint|, start: uint) {
-        let mut visited = Vec::from_fn(self.width(), |_| 0);
-        let mut queue = Vec::new();
-        queue.push(start);
-        // Continue looping until all vertices are visited.
-        while queue.len() != 0 {
-            let current = queue.remove(0).unwrap();
-            if *visited.get(current) == 1 {
-                continue;
-            }
-            closure(self, current);
-            *visited.get_mut(current) = 1;
-            for x in self.neighbors(current).iter() {
-                queue.push(*x);
+}
+
+// Both operations copy the whole matrix into one of the new size.
+impl<W: Clone> DynamicGraph<W> for VectorMatrix<Option<W>> {
+    fn add_vertex(&mut self) -> uint {
+        let old = self.width;
+        let mut elements = Vec::with_capacity((old + 1) * (old + 1));
+        for row in range(0, old) {
+            elements.push_all(self.elements.slice(row*old, row*old+old));
+            elements.push(None);
+        }
+        elements.grow(old + 1, &None);
+        self.elements = elements;
+        self.height = old + 1;
+        self.width = old + 1;
+        old
+    }
+    fn remove_vertex(&mut self, v: uint) -> Result<Vec<Option<uint>>, ~str> {
+        let old = self.width;
+        try!(check_bounds(old, v, v));
+        let mut elements = Vec::with_capacity((old - 1) * (old - 1));
+        for row in range(0, old) {
+            for col in range(0, old) {
+                if row != v && col != v {
+                    elements.push(self.elements.get(row*old + col).clone());
+                }
             }
         }
+        self.elements = elements;
+        self.height = old - 1;
+        self.width = old - 1;
+        Ok(removal_remap(old, v))
     }
 }
 
 #[test]
 fn test_matrix_impl() {
-    let mut matrix: ~VectorMatrix = Matrix::zero(2,2);
+    let mut matrix: ~VectorMatrix<int> = Matrix::zero(2,2);
     let mut res = matrix.set(1,1,4);
     assert!(res.is_ok())
     res = matrix.at(1,1);
@@ -148,13 +351,13 @@ fn test_matrix_impl() {
 
 #[test]
 fn test_matrix_set_bounds() {
-    let mut matrix: ~VectorMatrix = Matrix::zero(2,2);
+    let mut matrix: ~VectorMatrix<int> = Matrix::zero(2,2);
     assert!(matrix.set(2,2,5).is_err());
 }
 
 #[test]
 fn test_graph_neighbors() {
-    let mut graph: ~VectorMatrix = Graph::new(3);
+    let mut graph: ~AdjacencyMatrix<int> = MutableGraph::new(3);
     assert!(!graph.adjacent(1, 2).ok().unwrap());
     let mut res = graph.add(0,1,1);
     assert!(res.is_ok());
@@ -168,7 +371,7 @@ fn test_graph_neighbors() {
 
 #[test]
 fn test_graph_adjacent() {
-    let mut graph: ~VectorMatrix = Graph::new(3);
+    let mut graph: ~AdjacencyMatrix<int> = MutableGraph::new(3);
     assert!(!graph.adjacent(1, 2).ok().unwrap());
     let mut res = graph.add(0,1,1);
     assert!(res.is_ok());
@@ -190,7 +393,7 @@ fn test_graph_adjacent() {
  */
 #[test]
 fn test_graph_DFS() {
-    let mut graph: ~VectorMatrix = Graph::new(5);
+    let mut graph: ~AdjacencyMatrix<int> = MutableGraph::new(5);
     let mut res = graph.add(0,1,1);
     assert!(res.is_ok());
     res = graph.add(0,2,1);
@@ -205,12 +408,13 @@ fn test_graph_DFS() {
     assert!(res.is_ok());
     res = graph.add(3,0,1);
     assert!(res.is_ok());
-    fn test_DFS_closure(graph: &VectorMatrix, vec: &mut Vec<uint>) {
-        let closure = |_: &VectorMatrix, v: uint| {
+    fn test_DFS_closure(graph: &AdjacencyMatrix<int>, vec: &mut Vec<uint>) {
+        let closure = |_: &AdjacencyMatrix<int>, v: uint| {
             stdio::println(format!("closure called on vertex {}\n", v));
             (*vec).push(v);
+            Continue
         };
-        graph.depth_first_search(closure, 0);
+        assert!(graph.depth_first_search(closure, 0).is_none());
     }
     let mut dfs_vec = Vec::new();
     test_DFS_closure(graph, &mut dfs_vec);
@@ -234,7 +438,7 @@ fn test_graph_DFS() {
  */
 #[test]
 fn test_graph_BFS() {
-    let mut graph: ~VectorMatrix = Graph::new(5);
+    let mut graph: ~AdjacencyMatrix<int> = MutableGraph::new(5);
     let mut res = graph.add(0,1,1);
     assert!(res.is_ok());
     res = graph.add(0,2,1);
@@ -249,12 +453,13 @@ fn test_graph_BFS() {
     assert!(res.is_ok());
     res = graph.add(3,0,1);
     assert!(res.is_ok());
-    fn test_BFS_closure(graph: &VectorMatrix, vec: &mut Vec<uint>) {
-        let closure = |_: &VectorMatrix, v: uint| {
+    fn test_BFS_closure(graph: &AdjacencyMatrix<int>, vec: &mut Vec<uint>) {
+        let closure = |_: &AdjacencyMatrix<int>, v: uint| {
             stdio::println(format!("closure called on vertex {}\n", v));
             (*vec).push(v);
+            Continue
         };
-        graph.breadth_first_search(closure, 0);
+        assert!(graph.breadth_first_search(closure, 0).is_none());
     }
     let mut dfs_vec = Vec::new();
     test_BFS_closure(graph, &mut dfs_vec);
@@ -265,4 +470,116 @@ fn test_graph_BFS() {
     }
 }
 
+/*
+ *  The example graph shared by the search tests, built in any representation:
+ *  V 0 1 2 3 4
+ *  0 0 1 1 0 0
+ *  1 0 0 0 0 1
+ *  2 0 0 0 1 1
+ *  3 1 0 0 0 0
+ *  4 0 0 0 1 0
+ *
+ */
+#[cfg(test)]
+pub fn search_graph<G: MutableGraph<int>>() -> ~G {
+    let mut graph: ~G = MutableGraph::new(5);
+    let edges = [(0u,1u), (0,2), (2,3), (2,4), (1,4), (4,3), (3,0)];
+    for &(x, y) in edges.iter() {
+        assert!(graph.add(x, y, 1).is_ok());
+    }
+    graph
+}
+
+#[test]
+fn test_graph_search_stop() {
+    let graph: ~AdjacencyMatrix<int> = search_graph();
+    let mut dfs_vec = Vec::new();
+    let stopped = graph.depth_first_search(|_, v| {
+        dfs_vec.push(v);
+        if v == 4 { Stop } else { Continue }
+    }, 0);
+    assert_eq!(stopped, Some(4));
+    assert_eq!(dfs_vec, vec!(0u, 2, 4));
+    let mut bfs_vec = Vec::new();
+    let stopped = graph.breadth_first_search(|_, v| {
+        bfs_vec.push(v);
+        if v == 2 { Stop } else { Continue }
+    }, 0);
+    assert_eq!(stopped, Some(2));
+    assert_eq!(bfs_vec, vec!(0u, 1, 2));
+}
 
+#[test]
+fn test_graph_search_prune() {
+    let graph: ~AdjacencyMatrix<int> = search_graph();
+    let mut dfs_vec = Vec::new();
+    graph.depth_first_search(|_, v| {
+        dfs_vec.push(v);
+        if v == 2 { Prune } else { Continue }
+    }, 0);
+    assert_eq!(dfs_vec, vec!(0u, 2, 1, 4, 3));
+    let mut bfs_vec = Vec::new();
+    graph.breadth_first_search(|_, v| {
+        bfs_vec.push(v);
+        if v == 1 { Prune } else { Continue }
+    }, 0);
+    assert_eq!(bfs_vec, vec!(0u, 1, 2, 3, 4));
+    let mut pruned = Vec::new();
+    graph.depth_first_search(|_, v| { pruned.push(v); Prune }, 0);
+    assert_eq!(pruned, vec!(0u));
+}
+
+#[test]
+fn test_graph_dfs_iter() {
+    let graph: ~AdjacencyMatrix<int> = search_graph();
+    let order: Vec<uint> = graph.dfs_iter(0).collect();
+    assert_eq!(order, vec!(0u, 2, 4, 3, 1));
+    let first_two: Vec<uint> = graph.dfs_iter(3).take(2).collect();
+    assert_eq!(first_two, vec!(3u, 0));
+}
+
+#[test]
+fn test_graph_bfs_iter() {
+    let graph: ~AdjacencyMatrix<int> = search_graph();
+    let steps: Vec<(uint, uint, Option<uint>)> = graph.bfs_iter(0).collect();
+    assert_eq!(steps, vec!((0u, 0u, None), (1, 1, Some(0u)), (2, 1, Some(0)),
+                           (4, 2, Some(1)), (3, 2, Some(2))));
+    let mut interleaved = Vec::new();
+    for (dfs, (bfs, _, _)) in graph.dfs_iter(0).zip(graph.bfs_iter(0)) {
+        interleaved.push(dfs);
+        interleaved.push(bfs);
+    }
+    assert_eq!(interleaved, vec!(0u, 0, 2, 1, 4, 2, 3, 4, 1, 3));
+}
+
+#[test]
+fn test_graph_edge_values() {
+    let mut graph: ~AdjacencyMatrix<f64> = MutableGraph::new(3);
+    assert_eq!(graph.get_edge_value(0, 1).ok().unwrap(), None);
+    assert!(graph.set_edge_value(0, 1, 2.5).is_err());
+    assert!(graph.add(0, 1, 0.0).is_ok());
+    assert!(graph.adjacent(0, 1).ok().unwrap());
+    assert!(graph.set_edge_value(0, 1, 2.5).is_ok());
+    assert_eq!(graph.get_edge_value(0, 1).ok().unwrap(), Some(2.5));
+    assert_eq!(graph.delete(0, 1).ok().unwrap(), Some(2.5));
+    assert_eq!(graph.delete(0, 1).ok().unwrap(), None);
+    assert!(graph.neighbors(0).is_empty());
+}
+
+#[test]
+fn test_graph_add_remove_vertex() {
+    let mut graph: ~AdjacencyMatrix<int> = search_graph();
+    assert_eq!(graph.add_vertex(), 5);
+    assert_eq!(graph.vertices(), 6);
+    assert!(graph.neighbors(5).is_empty());
+    assert!(graph.add(5, 0, 1).is_ok());
+    assert!(graph.add(3, 5, 1).is_ok());
+    let remap = graph.remove_vertex(2).ok().unwrap();
+    assert_eq!(remap, vec!(Some(0u), Some(1), None, Some(2), Some(3), Some(4)));
+    assert_eq!(graph.vertices(), 5);
+    assert_eq!(graph.neighbors(0), vec!(1u));
+    assert_eq!(graph.neighbors(2), vec!(0u, 4));
+    assert_eq!(graph.neighbors(3), vec!(2u));
+    assert_eq!(graph.neighbors(4), vec!(0u));
+    assert!(graph.remove_vertex(5).is_err());
+}
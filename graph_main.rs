@@ -5,7 +5,25 @@
  * different graphing algorithms using my
  * graph module.
  */
+#![feature(unsafe_destructor)]
+
 mod graph;
+mod adjacency_list;
+mod assignment;
+mod bipartite;
+mod csr;
+mod dag;
+mod f_heap;
+mod flow;
+mod matching;
+mod mst;
+mod multigraph;
+mod property;
+mod scc;
+mod shortest_path;
+mod traversal;
+mod undirected;
+mod union_find;
 
 fn main() {
 
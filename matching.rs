@@ -0,0 +1,256 @@
+/*
+ * Christopher Piraino
+ *
+ * Maximum matchings in bipartite graphs.
+ */
+extern crate collections = "collections#0.11-pre";
+use collections::ringbuf::RingBuf;
+use collections::deque::Deque;
+use std::vec::Vec;
+use std::result::Result;
+use graph::Graph;
+use bipartite::{Side, Left, is_bipartite, undirected_adjacency};
+#[test]
+use bipartite::{BipartiteGraph, Right};
+#[test]
+use graph::MutableGraph;
+#[test]
+use adjacency_list::AdjacencyList;
+#[test]
+use undirected::UndirectedMatrix;
+
+/*  A maximum matching along with a minimum vertex cover. By König's theorem
+ *  the cover has exactly one vertex per matched edge, which certifies that no
+ *  larger matching exists.
+ */
+pub struct Matching {
+    // The matched edges as (left, right) pairs, ordered by left vertex.
+    pub pairs: Vec<(uint, uint)>,
+    // The vertex each vertex is matched with, if any.
+    pub mate: Vec<Option<uint>>,
+    // The vertices of the cover, in increasing order.
+    pub cover: Vec<uint>
+}
+
+/*  Hopcroft-Karp, which finds a maximum matching in O(E sqrt(V)) time. The
+ *  sides are found with is_bipartite, so a graph that is not bipartite gives
+ *  back its odd cycle instead. Edges are treated as undirected, so they may be
+ *  stored in one direction or both.
+ */
+pub fn hopcroft_karp<W, G: Graph<W>>(graph: &G) -> Result<Matching, Vec<uint>> {
+    let side = try!(is_bipartite(graph));
+    Ok(maximum_matching(undirected_adjacency(graph), &side))
+}
+
+/*  Hopcroft-Karp with the sides given rather than found, such as the partition
+ *  of a BipartiteGraph, which is needed whenever isolated vertices must stay
+ *  on the side they were declared on. Fails if an edge joins two vertices on
+ *  the same side.
+ */
+pub fn hopcroft_karp_with<W, G: Graph<W>>(graph: &G, side: &Vec<Side>) -> Result<Matching, ~str> {
+    if side.len() != graph.vertices() {
+        return Err(format!("{} sides were given for a graph with {} vertices.",
+                           side.len(), graph.vertices()));
+    }
+    let adj = undirected_adjacency(graph);
+    for u in range(0, adj.len()) {
+        for &v in adj.get(u).iter() {
+            if *side.get(u) == *side.get(v) {
+                return Err(format!("({}, {}) joins two vertices on the same side.", u, v));
+            }
+        }
+    }
+    Ok(maximum_matching(adj, side))
+}
+
+/*  Each phase layers the graph with a breadth first search from the unmatched
+ *  left vertices, then a depth first search from each of them augments along
+ *  vertex-disjoint shortest paths. Only the edges leaving left vertices are
+ *  kept, since every edge appears in the adjacency of both of its ends.
+ */
+fn maximum_matching(undirected: Vec<Vec<uint>>, side: &Vec<Side>) -> Matching {
+    let vertices = undirected.len();
+    let left: Vec<uint> = range(0, vertices).filter(|&v| *side.get(v) == Left).collect();
+    let adj: Vec<Vec<uint>> = undirected.move_iter().enumerate().map(|(v, list)| {
+        if *side.get(v) == Left { list } else { Vec::new() }
+    }).collect();
+    let mut mate: Vec<Option<uint>> = Vec::from_elem(vertices, None);
+    loop {
+        // Layer of each left vertex, and the layer at which an unmatched right
+        // vertex is first reached, which is where every path this phase ends.
+        let mut layer: Vec<Option<uint>> = Vec::from_elem(vertices, None);
+        let mut limit = None;
+        let mut queue = RingBuf::new();
+        for &u in left.iter() {
+            if mate.get(u).is_none() {
+                *layer.get_mut(u) = Some(0u);
+                queue.push_back(u);
+            }
+        }
+        while !queue.is_empty() {
+            let u = queue.pop_front().unwrap();
+            let depth = (*layer.get(u)).unwrap();
+            if limit.is_some() && Some(depth) > limit {
+                break;
+            }
+            for &v in adj.get(u).iter() {
+                match *mate.get(v) {
+                    None => if limit.is_none() { limit = Some(depth) },
+                    Some(w) => if layer.get(w).is_none() {
+                        *layer.get_mut(w) = Some(depth + 1);
+                        queue.push_back(w);
+                    }
+                }
+            }
+        }
+        if limit.is_none() {
+            break;
+        }
+        // Index of the next edge to try from each left vertex.
+        let mut position = Vec::from_elem(vertices, 0u);
+        for &root in left.iter() {
+            if mate.get(root).is_some() || *layer.get(root) != Some(0) {
+                continue;
+            }
+            let mut stack = vec!(root);
+            while !stack.is_empty() {
+                let u = *stack.last().unwrap();
+                let i = *position.get(u);
+                if i == adj.get(u).len() {
+                    // A dead end, which no later search this phase should enter.
+                    *layer.get_mut(u) = None;
+                    stack.pop();
+                    continue;
+                }
+                *position.get_mut(u) = i + 1;
+                let v = *adj.get(u).get(i);
+                let mated = *mate.get(v);
+                match mated {
+                    None if *layer.get(u) == limit => {
+                        // Each vertex on the stack takes the edge it last tried.
+                        for &w in stack.iter() {
+                            let x = *adj.get(w).get(*position.get(w) - 1);
+                            *mate.get_mut(w) = Some(x);
+                            *mate.get_mut(x) = Some(w);
+                        }
+                        break;
+                    }
+                    Some(w) if *layer.get(w) == (*layer.get(u)).map(|d| d + 1) => stack.push(w),
+                    _ => {}
+                }
+            }
+        }
+    }
+    let cover = konig_cover(&adj, &left, &mate);
+    let pairs = left.iter().filter_map(|&u| (*mate.get(u)).map(|v| (u, v))).collect();
+    Matching { pairs: pairs, mate: mate, cover: cover }
+}
+
+/*  The vertices reachable from the unmatched left vertices by alternating
+ *  paths are marked. The cover is then the unmarked left vertices together
+ *  with the marked right vertices.
+ */
+fn konig_cover(adj: &Vec<Vec<uint>>, left: &Vec<uint>, mate: &Vec<Option<uint>>) -> Vec<uint> {
+    let vertices = adj.len();
+    let mut marked = Vec::from_elem(vertices, false);
+    let mut is_left = Vec::from_elem(vertices, false);
+    let mut stack = Vec::new();
+    for &u in left.iter() {
+        *is_left.get_mut(u) = true;
+        if mate.get(u).is_none() {
+            *marked.get_mut(u) = true;
+            stack.push(u);
+        }
+    }
+    while !stack.is_empty() {
+        let u = stack.pop().unwrap();
+        for &v in adj.get(u).iter() {
+            if *marked.get(v) || *mate.get(u) == Some(v) {
+                continue;
+            }
+            *marked.get_mut(v) = true;
+            match *mate.get(v) {
+                Some(w) if !*marked.get(w) => {
+                    *marked.get_mut(w) = true;
+                    stack.push(w);
+                }
+                _ => {}
+            }
+        }
+    }
+    range(0, vertices).filter(|&v| *is_left.get(v) != *marked.get(v)).collect()
+}
+
+/*
+ *  Left vertices 0 to 3, right vertices 4 to 7:
+ *  0 - 4, 0 - 5
+ *  1 - 4
+ *  2 - 5, 2 - 6, 2 - 7
+ *  3 - 4
+ *
+ */
+#[test]
+fn test_hopcroft_karp() {
+    let mut graph = BipartiteGraph::new(4, 4);
+    let edges = [(0u,0u), (0,1), (1,0), (2,1), (2,2), (2,3), (3,0)];
+    for &(l, r) in edges.iter() {
+        assert!(graph.add(l, r, 1).is_ok());
+    }
+    let matching = hopcroft_karp(&graph).ok().unwrap();
+    assert_eq!(matching.pairs.len(), 3);
+    assert_eq!(*matching.pairs.get(0), (0u, 5u));
+    for &(u, v) in matching.pairs.iter() {
+        assert!(graph.adjacent(u, v).ok().unwrap());
+        assert_eq!(*matching.mate.get(v), Some(u));
+    }
+    assert_eq!(matching.cover.len(), 3);
+    for &(l, r) in edges.iter() {
+        let (u, v) = (graph.vertex(Left, l), graph.vertex(Right, r));
+        assert!(matching.cover.contains(&u) || matching.cover.contains(&v));
+    }
+}
+
+#[test]
+fn test_hopcroft_karp_perfect_and_odd() {
+    // A cycle of length 6 has a perfect matching, and one of length 5 is not bipartite.
+    let mut graph: ~UndirectedMatrix<int> = MutableGraph::new(6);
+    for v in range(0u, 6) {
+        assert!(graph.add(v, (v + 1) % 6, 1).is_ok());
+    }
+    let matching = hopcroft_karp(&*graph).ok().unwrap();
+    assert_eq!(matching.pairs.len(), 3);
+    assert!(matching.mate.iter().all(|m| m.is_some()));
+    assert_eq!(matching.cover.len(), 3);
+    let mut odd: ~UndirectedMatrix<int> = MutableGraph::new(5);
+    for v in range(0u, 5) {
+        assert!(odd.add(v, (v + 1) % 5, 1).is_ok());
+    }
+    assert_eq!(hopcroft_karp(&*odd).err().unwrap().len(), 5);
+}
+
+#[test]
+fn test_hopcroft_karp_mixed_directions() {
+    // The path 0 - 1 - 3 - 2, stored as 0 -> 1, 2 -> 3 and 3 -> 1.
+    let mut graph: ~AdjacencyList<int> = MutableGraph::new(4);
+    for &(x, y) in [(0u,1u), (2,3), (3,1)].iter() {
+        assert!(graph.add(x, y, 1).is_ok());
+    }
+    let matching = hopcroft_karp(&*graph).ok().unwrap();
+    assert_eq!(matching.pairs, vec!((0u, 1u), (3, 2)));
+    assert_eq!(matching.cover.len(), 2);
+}
+
+#[test]
+fn test_hopcroft_karp_declared_sides() {
+    // Right vertices 1 and 2 are isolated, which is_bipartite would color left.
+    let mut graph = BipartiteGraph::new(2, 3);
+    assert!(graph.add(0, 0, 1).is_ok());
+    assert!(graph.add(1, 0, 1).is_ok());
+    let matching = hopcroft_karp_with(&graph, &graph.partition()).ok().unwrap();
+    assert_eq!(matching.pairs.len(), 1);
+    assert_eq!(matching.cover, vec!(2u));
+    assert!(matching.mate.slice_from(3).iter().all(|m| m.is_none()));
+    let wrong = vec!(Left, Left, Left, Right, Right);
+    assert!(hopcroft_karp_with(&graph, &wrong).is_err());
+    assert!(hopcroft_karp_with(&graph, &vec!(Left, Right)).is_err());
+}
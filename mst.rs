@@ -0,0 +1,161 @@
+/*
+ * Christopher Piraino
+ *
+ * Minimum spanning trees of undirected
+ * graphs, where every edge is stored in
+ * both directions.
+ */
+use std::vec::Vec;
+use std::result::Result;
+use std::num::Zero;
+use graph::{Graph, Weight};
+use f_heap::{FHeap, FibEntry};
+use union_find::UnionFind;
+#[test]
+use graph::{MutableGraph, AdjacencyMatrix};
+#[test]
+use undirected::UndirectedMatrix;
+
+/*  The edges of a minimum spanning tree as (x, y, value) triples along with
+ *  their total value. A graph that is not connected gets a spanning forest
+ *  with one tree per component.
+ */
+pub struct SpanningTree<W> {
+    pub edges: Vec<(uint, uint, W)>,
+    pub weight: W
+}
+
+/*  Prim's algorithm. Each tree is grown from a root by repeatedly adding the
+ *  cheapest edge that leaves it, with the Fibonacci heap keyed by the cheapest
+ *  known edge to each vertex outside the tree.
+ */
+pub fn prim<W: Weight, G: Graph<W>>(graph: &G) -> Result<SpanningTree<W>, ~str> {
+    let vertices = graph.vertices();
+    let mut in_tree = Vec::from_elem(vertices, false);
+    // The cheapest known edge joining each vertex to the tree.
+    let mut cheapest: Vec<Option<W>> = Vec::from_elem(vertices, None);
+    let mut parent: Vec<Option<uint>> = Vec::from_elem(vertices, None);
+    let mut entries: Vec<Option<FibEntry<W, uint>>> = Vec::from_elem(vertices, None);
+    let mut heap = FHeap::new();
+    let mut edges = Vec::new();
+    let mut weight: W = Zero::zero();
+    for root in range(0, vertices) {
+        if *in_tree.get(root) {
+            continue;
+        }
+        *entries.get_mut(root) = Some(heap.insert(Zero::zero(), root));
+        while !heap.is_empty() {
+            let (_, u) = heap.delete_min();
+            *entries.get_mut(u) = None;
+            *in_tree.get_mut(u) = true;
+            match *parent.get(u) {
+                Some(p) => {
+                    let value = cheapest.get(u).clone().unwrap();
+                    weight = weight + value;
+                    edges.push((p, u, value));
+                }
+                None => {}
+            }
+            for &v in graph.neighbors(u).iter() {
+                if *in_tree.get(v) {
+                    continue;
+                }
+                let value = try!(graph.get_edge_value(u, v)).unwrap();
+                match *cheapest.get(v) {
+                    Some(ref old) if *old <= value => continue,
+                    Some(ref old) => heap.decrease_key(entries.get(v).clone().unwrap(), *old - value),
+                    None => *entries.get_mut(v) = Some(heap.insert(value.clone(), v))
+                }
+                *cheapest.get_mut(v) = Some(value);
+                *parent.get_mut(v) = Some(u);
+            }
+        }
+    }
+    Ok(SpanningTree { edges: edges, weight: weight })
+}
+
+/*  Kruskal's algorithm. Edges are considered from cheapest to most expensive,
+ *  and an edge is kept whenever it joins two different trees of the forest
+ *  built so far. Self loops can never be part of the tree and are skipped.
+ */
+pub fn kruskal<W: Weight, G: Graph<W>>(graph: &G) -> Result<SpanningTree<W>, ~str> {
+    let vertices = graph.vertices();
+    let mut candidates = Vec::new();
+    for u in range(0, vertices) {
+        for &v in graph.neighbors(u).iter() {
+            if u != v {
+                candidates.push((u, v, try!(graph.get_edge_value(u, v)).unwrap()));
+            }
+        }
+    }
+    // Weights are only partially ordered, so incomparable values sort as equal.
+    candidates.as_mut_slice().sort_by(|&(_, _, ref a), &(_, _, ref b)| {
+        if *a < *b { Less } else if *b < *a { Greater } else { Equal }
+    });
+    let mut sets = UnionFind::new(vertices);
+    let mut edges = Vec::new();
+    let mut weight: W = Zero::zero();
+    for &(u, v, ref value) in candidates.iter() {
+        if sets.union(u, v) {
+            edges.push((u, v, value.clone()));
+            weight = weight + *value;
+        }
+    }
+    Ok(SpanningTree { edges: edges, weight: weight })
+}
+
+#[cfg(test)]
+fn undirected_graph(vertices: uint, edges: &[(uint, uint, int)]) -> ~AdjacencyMatrix<int> {
+    let mut graph: ~AdjacencyMatrix<int> = MutableGraph::new(vertices);
+    for &(x, y, val) in edges.iter() {
+        assert!(graph.add(x, y, val).is_ok());
+        assert!(graph.add(y, x, val).is_ok());
+    }
+    graph
+}
+
+#[test]
+fn test_minimum_spanning_tree() {
+    let graph = undirected_graph(9, &[(0,1,4), (0,7,8), (1,2,8), (1,7,11), (2,3,7),
+                                      (2,8,2), (2,5,4), (3,4,9), (3,5,14), (4,5,10),
+                                      (5,6,2), (6,7,1), (6,8,6), (7,8,7)]);
+    let by_prim = prim(&*graph).ok().unwrap();
+    let by_kruskal = kruskal(&*graph).ok().unwrap();
+    assert_eq!(by_prim.weight, 37);
+    assert_eq!(by_prim.edges.len(), 8);
+    assert_eq!(by_kruskal.weight, 37);
+    assert_eq!(by_kruskal.edges.len(), 8);
+    for &(x, y, val) in by_prim.edges.iter().chain(by_kruskal.edges.iter()) {
+        assert_eq!(graph.get_edge_value(x, y).ok().unwrap(), Some(val));
+    }
+}
+
+#[test]
+fn test_minimum_spanning_forest() {
+    let graph = undirected_graph(6, &[(0,1,3), (1,2,1), (0,2,2), (3,4,5), (4,4,1)]);
+    let by_prim = prim(&*graph).ok().unwrap();
+    let by_kruskal = kruskal(&*graph).ok().unwrap();
+    assert_eq!(by_prim.weight, 8);
+    assert_eq!(by_prim.edges.len(), 3);
+    assert_eq!(by_kruskal.weight, 8);
+    assert_eq!(by_kruskal.edges.len(), 3);
+}
+
+#[test]
+fn test_minimum_spanning_tree_undirected_matrix() {
+    // Each edge is added once, and the matrix reports it in both directions.
+    let mut graph: ~UndirectedMatrix<int> = MutableGraph::new(6);
+    let edges = [(0u,1u,3), (1,2,1), (0,2,2), (3,4,5), (4,4,1), (2,5,4)];
+    for &(x, y, val) in edges.iter() {
+        assert!(graph.add(x, y, val).is_ok());
+    }
+    let by_prim = prim(&*graph).ok().unwrap();
+    let by_kruskal = kruskal(&*graph).ok().unwrap();
+    assert_eq!(by_prim.weight, 12);
+    assert_eq!(by_prim.edges.len(), 4);
+    assert_eq!(by_kruskal.weight, 12);
+    assert_eq!(by_kruskal.edges.len(), 4);
+    for &(x, y, val) in by_prim.edges.iter().chain(by_kruskal.edges.iter()) {
+        assert_eq!(graph.get_edge_value(y, x).ok().unwrap(), Some(val));
+    }
+}
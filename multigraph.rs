@@ -0,0 +1,166 @@
+/*
+ * Christopher Piraino
+ *
+ * A directed multigraph, which can have
+ * any number of parallel edges between
+ * two vertices as well as self loops.
+ */
+use std::vec::Vec;
+use std::result::Result;
+use std::mem::replace;
+use graph::{Graph, check_bounds};
+#[test]
+use shortest_path::dijkstra;
+
+/*  Edges are addressed by the id returned when they are added. Ids are never
+ *  reused, so the id of a deleted edge stays invalid rather than coming to
+ *  mean a different edge.
+ */
+pub struct Multigraph<W> {
+    // Every edge ever added as (source, target, value), indexed by id, with
+    // None for the edges that have been deleted.
+    edges: Vec<Option<(uint, uint, W)>>,
+    // Ids of the edges leaving each vertex, in the order they were added.
+    out: Vec<Vec<uint>>
+}
+
+impl<W: Clone> Multigraph<W> {
+    pub fn new(vertices: uint) -> Multigraph<W> {
+        Multigraph { edges: Vec::new(), out: Vec::from_fn(vertices, |_| Vec::new()) }
+    }
+    // Adds an edge from x to y, alongside any already there. Returns its id.
+    pub fn add(&mut self, x: uint, y: uint, val: W) -> Result<uint, ~str> {
+        try!(self.check_bounds(x, y));
+        let id = self.edges.len();
+        self.edges.push(Some((x, y, val)));
+        self.out.get_mut(x).push(id);
+        Ok(id)
+    }
+    // Removes an edge, returning its value.
+    pub fn delete(&mut self, id: uint) -> Result<W, ~str> {
+        try!(self.entry(id));
+        let (x, _, value) = replace(self.edges.get_mut(id), None).unwrap();
+        self.out.get_mut(x).retain(|&e| e != id);
+        Ok(value)
+    }
+    pub fn get(&self, id: uint) -> Result<W, ~str> {
+        let &(_, _, ref value) = try!(self.entry(id));
+        Ok(value.clone())
+    }
+    pub fn set(&mut self, id: uint, val: W) -> Result<W, ~str> {
+        try!(self.entry(id));
+        match *self.edges.get_mut(id) {
+            Some((_, _, ref mut value)) => *value = val.clone(),
+            None => {}
+        }
+        Ok(val)
+    }
+    // The (source, target) pair of an edge.
+    pub fn endpoints(&self, id: uint) -> Result<(uint, uint), ~str> {
+        let &(x, y, _) = try!(self.entry(id));
+        Ok((x, y))
+    }
+    // The number of edges, counting every parallel edge.
+    pub fn edge_count(&self) -> uint {
+        self.out.iter().fold(0, |count, ids| count + ids.len())
+    }
+    // Ids of the edges from x to y, in the order they were added.
+    pub fn edges_between(&self, x: uint, y: uint) -> Vec<uint> {
+        self.out.get(x).iter().map(|&id| id).filter(|&id| {
+            self.endpoints(id).unwrap().val1() == y
+        }).collect()
+    }
+    /*  Like neighbors, but reports every edge leaving x as a (target, id) pair,
+     *  so a vertex joined to x by parallel edges appears once for each of them.
+     *  Pairs are sorted by target and then by id.
+     */
+    pub fn neighbor_edges(&self, x: uint) -> Vec<(uint, uint)> {
+        let mut pairs: Vec<(uint, uint)> = self.out.get(x).iter().map(|&id| {
+            (self.endpoints(id).unwrap().val1(), id)
+        }).collect();
+        pairs.as_mut_slice().sort();
+        pairs
+    }
+    fn entry<'a>(&'a self, id: uint) -> Result<&'a (uint, uint, W), ~str> {
+        if id < self.edges.len() {
+            match *self.edges.get(id) {
+                Some(ref edge) => return Ok(edge),
+                None => {}
+            }
+        }
+        Err(format!("There is no edge with id {}.", id))
+    }
+    fn check_bounds(&self, x: uint, y: uint) -> Result<(), ~str> {
+        check_bounds(self.out.len(), x, y)
+    }
+}
+
+/*  Seen as a Graph, the parallel edges from x to y act as a single edge with
+ *  the value of the cheapest of them, which is the one any shortest path or
+ *  spanning tree would use.
+ */
+impl<W: Clone + Ord> Graph<W> for Multigraph<W> {
+    fn vertices(&self) -> uint {
+        self.out.len()
+    }
+    fn adjacent(&self, x: uint, y: uint) -> Result<bool, ~str> {
+        try!(self.check_bounds(x, y));
+        Ok(!self.edges_between(x, y).is_empty())
+    }
+    fn neighbors(&self, x: uint) -> Vec<uint> {
+        let mut targets: Vec<uint> = self.neighbor_edges(x).iter().map(|&(y, _)| y).collect();
+        targets.dedup();
+        targets
+    }
+    fn get_edge_value(&self, x: uint, y: uint) -> Result<Option<W>, ~str> {
+        try!(self.check_bounds(x, y));
+        let mut cheapest: Option<W> = None;
+        for &id in self.edges_between(x, y).iter() {
+            let value = self.get(id).unwrap();
+            if cheapest.as_ref().map_or(true, |c| value < *c) {
+                cheapest = Some(value);
+            }
+        }
+        Ok(cheapest)
+    }
+}
+
+#[test]
+fn test_multigraph_parallel_edges() {
+    let mut graph = Multigraph::new(3);
+    let slow = graph.add(0, 1, 5).ok().unwrap();
+    let fast = graph.add(0, 1, 3).ok().unwrap();
+    let other = graph.add(0, 2, 4).ok().unwrap();
+    assert!(graph.add(0, 3, 1).is_err());
+    assert_eq!(graph.edge_count(), 3);
+    assert_eq!(graph.edges_between(0, 1), vec!(slow, fast));
+    assert_eq!(graph.neighbors(0), vec!(1u, 2));
+    assert_eq!(graph.neighbor_edges(0), vec!((1u, slow), (1u, fast), (2u, other)));
+    assert_eq!(graph.get_edge_value(0, 1).ok().unwrap(), Some(3));
+    assert!(graph.set(slow, 2).is_ok());
+    assert_eq!(graph.get_edge_value(0, 1).ok().unwrap(), Some(2));
+    assert_eq!(graph.delete(slow).ok().unwrap(), 2);
+    assert!(graph.get(slow).is_err());
+    assert!(graph.delete(slow).is_err());
+    assert_eq!(graph.get(fast).ok().unwrap(), 3);
+    assert_eq!(graph.endpoints(fast).ok().unwrap(), (0u, 1u));
+    assert_eq!(graph.edge_count(), 2);
+    assert!(graph.delete(fast).is_ok());
+    assert!(!graph.adjacent(0, 1).ok().unwrap());
+    assert_eq!(graph.get_edge_value(0, 1).ok().unwrap(), None);
+}
+
+#[test]
+fn test_multigraph_self_loops() {
+    let mut graph = Multigraph::new(3);
+    let first = graph.add(1, 1, 2).ok().unwrap();
+    let second = graph.add(1, 1, 2).ok().unwrap();
+    assert!(first != second);
+    assert!(graph.add(1, 2, 6).is_ok());
+    assert!(graph.add(0, 1, 1).is_ok());
+    assert!(graph.adjacent(1, 1).ok().unwrap());
+    assert_eq!(graph.neighbors(1), vec!(1u, 2));
+    assert_eq!(graph.neighbor_edges(1).len(), 3);
+    let paths = dijkstra(&graph, 0).ok().unwrap();
+    assert_eq!(paths.distance, vec!(Some(0), Some(1), Some(7)));
+}
@@ -0,0 +1,200 @@
+/*
+ * Christopher Piraino
+ *
+ * A property graph, which attaches data
+ * to its vertices and edges and looks its
+ * vertices up by key.
+ */
+extern crate collections = "collections#0.11-pre";
+use collections::hashmap::HashMap;
+use std::hash::Hash;
+use std::fmt::Show;
+use std::vec::Vec;
+use std::result::Result;
+use graph::{Graph, MutableGraph, DynamicGraph};
+use adjacency_list::AdjacencyList;
+#[test]
+use graph::AdjacencyMatrix;
+#[test]
+use shortest_path::dijkstra;
+
+/*  Vertices are identified by keys of type K and carry data of type V, and
+ *  edges carry data of type E. Each vertex also has an index, given in the
+ *  order the vertices were added, and the graph implements Graph over those
+ *  indices with the edge data as the edge values.
+ */
+pub struct PropertyGraph<K, V, E> {
+    keys: Vec<K>,
+    indices: HashMap<K, uint>,
+    data: Vec<V>,
+    edges: ~AdjacencyList<E>
+}
+
+impl<K: Hash + TotalEq + Clone + Show, V, E: Clone> PropertyGraph<K, V, E> {
+    pub fn new() -> PropertyGraph<K, V, E> {
+        PropertyGraph {
+            keys: Vec::new(),
+            indices: HashMap::new(),
+            data: Vec::new(),
+            edges: MutableGraph::new(0)
+        }
+    }
+    // Adds a vertex with the given key, or replaces the data of the vertex
+    // that already has it. Returns the index of the vertex.
+    pub fn add_vertex(&mut self, key: K, data: V) -> uint {
+        match self.index_of(&key) {
+            Some(v) => {
+                *self.data.get_mut(v) = data;
+                return v;
+            }
+            None => {}
+        }
+        let v = self.edges.add_vertex();
+        self.indices.insert(key.clone(), v);
+        self.keys.push(key);
+        self.data.push(data);
+        v
+    }
+    /*  Removes the vertex with the given key along with its edges. As with
+     *  DynamicGraph, the vertices after it move down by one index, and the
+     *  new index of every old vertex is returned.
+     */
+    pub fn remove_vertex(&mut self, key: &K) -> Result<Vec<Option<uint>>, ~str> {
+        let v = try!(self.lookup(key));
+        let remap = try!(self.edges.remove_vertex(v));
+        self.indices.remove(key);
+        self.keys.remove(v);
+        self.data.remove(v);
+        for (u, moved) in self.keys.iter().enumerate().skip(v) {
+            self.indices.insert(moved.clone(), u);
+        }
+        Ok(remap)
+    }
+    pub fn index_of(&self, key: &K) -> Option<uint> {
+        self.indices.find(key).map(|&v| v)
+    }
+    pub fn key<'a>(&'a self, v: uint) -> &'a K {
+        self.keys.get(v)
+    }
+    pub fn vertex<'a>(&'a self, v: uint) -> &'a V {
+        self.data.get(v)
+    }
+    pub fn vertex_mut<'a>(&'a mut self, v: uint) -> &'a mut V {
+        self.data.get_mut(v)
+    }
+    // Adds an edge between two vertices given by key, or replaces its data.
+    pub fn add_edge(&mut self, from: &K, to: &K, data: E) -> Result<E, ~str> {
+        let (x, y) = (try!(self.lookup(from)), try!(self.lookup(to)));
+        self.edges.add(x, y, data)
+    }
+    pub fn edge(&self, from: &K, to: &K) -> Result<Option<E>, ~str> {
+        let (x, y) = (try!(self.lookup(from)), try!(self.lookup(to)));
+        self.edges.get_edge_value(x, y)
+    }
+    pub fn remove_edge(&mut self, from: &K, to: &K) -> Result<Option<E>, ~str> {
+        let (x, y) = (try!(self.lookup(from)), try!(self.lookup(to)));
+        self.edges.delete(x, y)
+    }
+    // The keys of a list of vertices, such as a path found by an algorithm.
+    pub fn keys_of(&self, vertices: &[uint]) -> Vec<K> {
+        vertices.iter().map(|&v| self.key(v).clone()).collect()
+    }
+    /*  Copies the edges into a new graph with the same vertex indices and the
+     *  value of each edge computed from its data, for running the weighted
+     *  algorithms on one of the properties of the edges.
+     */
+    pub fn project<W, H: MutableGraph<W>>(&self, value: |&E| -> W) -> ~H {
+        let mut graph: ~H = MutableGraph::new(self.vertices());
+        for x in range(0, self.vertices()) {
+            for &y in self.neighbors(x).iter() {
+                let data = self.edges.get_edge_value(x, y).unwrap().unwrap();
+                graph.add(x, y, value(&data)).unwrap();
+            }
+        }
+        graph
+    }
+    fn lookup(&self, key: &K) -> Result<uint, ~str> {
+        match self.index_of(key) {
+            Some(v) => Ok(v),
+            None => Err(format!("There is no vertex with key {}.", *key))
+        }
+    }
+}
+
+impl<K, V, E: Clone> Graph<E> for PropertyGraph<K, V, E> {
+    fn vertices(&self) -> uint {
+        self.edges.vertices()
+    }
+    fn adjacent(&self, x: uint, y: uint) -> Result<bool, ~str> {
+        self.edges.adjacent(x, y)
+    }
+    fn neighbors(&self, x: uint) -> Vec<uint> {
+        self.edges.neighbors(x)
+    }
+    fn get_edge_value(&self, x: uint, y: uint) -> Result<Option<E>, ~str> {
+        self.edges.get_edge_value(x, y)
+    }
+}
+
+#[cfg(test)]
+#[deriving(Clone, Eq, Show)]
+struct Track {
+    minutes: int,
+    line: ~str
+}
+
+#[cfg(test)]
+fn railway() -> PropertyGraph<~str, uint, Track> {
+    let mut graph = PropertyGraph::new();
+    let stations = [(~"Central", 1u), (~"Harbour", 2), (~"Market", 1), (~"Airport", 3)];
+    for &(ref name, zone) in stations.iter() {
+        graph.add_vertex(name.clone(), zone);
+    }
+    let tracks = [(~"Central", ~"Harbour", 7, ~"Blue"), (~"Central", ~"Market", 2, ~"Red"),
+                  (~"Market", ~"Harbour", 3, ~"Red"), (~"Harbour", ~"Airport", 10, ~"Blue")];
+    for &(ref from, ref to, minutes, ref line) in tracks.iter() {
+        let track = Track { minutes: minutes, line: line.clone() };
+        assert!(graph.add_edge(from, to, track).is_ok());
+    }
+    graph
+}
+
+#[test]
+fn test_property_graph_lookup() {
+    let mut graph = railway();
+    assert_eq!(graph.vertices(), 4);
+    let market = graph.index_of(&~"Market").unwrap();
+    assert_eq!(*graph.key(market), ~"Market");
+    assert_eq!(*graph.vertex(market), 1);
+    assert_eq!(graph.add_vertex(~"Market", 2), market);
+    assert_eq!(*graph.vertex(market), 2);
+    assert!(graph.index_of(&~"Depot").is_none());
+    assert!(graph.add_edge(&~"Depot", &~"Central", Track { minutes: 1, line: ~"Red" }).is_err());
+    let track = graph.edge(&~"Market", &~"Harbour").ok().unwrap().unwrap();
+    assert_eq!(track.line, ~"Red");
+    assert_eq!(graph.remove_edge(&~"Market", &~"Harbour").ok().unwrap(), Some(track));
+    assert_eq!(graph.edge(&~"Market", &~"Harbour").ok().unwrap(), None);
+    let remap = graph.remove_vertex(&~"Harbour").ok().unwrap();
+    assert_eq!(remap, vec!(Some(0u), None, Some(1), Some(2)));
+    assert_eq!(graph.index_of(&~"Airport"), Some(2));
+    assert_eq!(*graph.key(1), ~"Market");
+    assert_eq!(*graph.vertex(2), 3);
+    assert!(graph.index_of(&~"Harbour").is_none());
+    assert_eq!(graph.neighbors(0), vec!(1u));
+}
+
+#[test]
+fn test_property_graph_algorithms() {
+    let graph = railway();
+    let central = graph.index_of(&~"Central").unwrap();
+    let airport = graph.index_of(&~"Airport").unwrap();
+    let reachable: Vec<uint> = graph.dfs_iter(central).collect();
+    assert_eq!(graph.keys_of(reachable.as_slice()),
+               vec!(~"Central", ~"Market", ~"Harbour", ~"Airport"));
+    let minutes: ~AdjacencyMatrix<int> = graph.project(|track| track.minutes);
+    let paths = dijkstra(&*minutes, central).ok().unwrap();
+    assert_eq!(*paths.distance.get(airport), Some(15));
+    let path = paths.path_to(airport).unwrap();
+    assert_eq!(graph.keys_of(path.as_slice()),
+               vec!(~"Central", ~"Market", ~"Harbour", ~"Airport"));
+}
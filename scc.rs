@@ -0,0 +1,223 @@
+/*
+ * Christopher Piraino
+ *
+ * Strongly connected components of a
+ * directed graph.
+ */
+use std::vec::Vec;
+use std::cmp::min;
+use graph::{Graph, MutableGraph};
+#[test]
+use graph::AdjacencyMatrix;
+#[test]
+use adjacency_list::AdjacencyList;
+#[test]
+use dag::is_dag;
+
+/*  The strongly connected components of a graph. Components are numbered in
+ *  topological order of the condensation, which has one vertex per component
+ *  and an edge of value 1 wherever an edge of the original graph joins two
+ *  different components.
+ */
+pub struct Components<H> {
+    pub component: Vec<uint>,
+    pub count: uint,
+    pub condensation: ~H
+}
+
+/*  Tarjan's algorithm. The depth first search is driven by an explicit stack
+ *  so that long paths cannot overflow the call stack. A vertex whose lowlink
+ *  equals its own index when it finishes is the root of a component, made up
+ *  of the vertices above it on the component stack.
+ */
+pub fn tarjan<W, G: Graph<W>, H: MutableGraph<uint>>(graph: &G) -> Components<H> {
+    let vertices = graph.vertices();
+    let adj: Vec<Vec<uint>> = range(0, vertices).map(|v| graph.neighbors(v)).collect();
+    let mut index: Vec<Option<uint>> = Vec::from_elem(vertices, None);
+    let mut lowlink = Vec::from_elem(vertices, 0u);
+    let mut on_stack = Vec::from_elem(vertices, false);
+    // Index of the next neighbor to explore for each vertex being visited.
+    let mut position = Vec::from_elem(vertices, 0u);
+    let mut component = Vec::from_elem(vertices, 0u);
+    let mut stack = Vec::new();
+    let mut counter = 0u;
+    let mut count = 0u;
+    for root in range(0, vertices) {
+        if index.get(root).is_some() {
+            continue;
+        }
+        let mut calls = vec!(root);
+        *index.get_mut(root) = Some(counter);
+        *lowlink.get_mut(root) = counter;
+        counter += 1;
+        stack.push(root);
+        *on_stack.get_mut(root) = true;
+        while !calls.is_empty() {
+            let u = *calls.last().unwrap();
+            let i = *position.get(u);
+            if i < adj.get(u).len() {
+                *position.get_mut(u) = i + 1;
+                let v = *adj.get(u).get(i);
+                match *index.get(v) {
+                    None => {
+                        *index.get_mut(v) = Some(counter);
+                        *lowlink.get_mut(v) = counter;
+                        counter += 1;
+                        stack.push(v);
+                        *on_stack.get_mut(v) = true;
+                        calls.push(v);
+                    }
+                    Some(v_index) => if *on_stack.get(v) {
+                        let low = min(*lowlink.get(u), v_index);
+                        *lowlink.get_mut(u) = low;
+                    }
+                }
+                continue;
+            }
+            calls.pop();
+            if Some(*lowlink.get(u)) == *index.get(u) {
+                loop {
+                    let w = stack.pop().unwrap();
+                    *on_stack.get_mut(w) = false;
+                    *component.get_mut(w) = count;
+                    if w == u {
+                        break;
+                    }
+                }
+                count += 1;
+            }
+            match calls.last() {
+                Some(&parent) => {
+                    let low = min(*lowlink.get(parent), *lowlink.get(u));
+                    *lowlink.get_mut(parent) = low;
+                }
+                None => {}
+            }
+        }
+    }
+    // Tarjan finds the components in reverse topological order.
+    for c in component.mut_iter() {
+        *c = count - 1 - *c;
+    }
+    condense(graph, component, count)
+}
+
+/*  Kosaraju's algorithm. A first depth first search records the order in
+ *  which vertices finish. Searching the transposed graph from each vertex in
+ *  reverse finishing order then reaches exactly one new component at a time.
+ */
+pub fn kosaraju<W, G: Graph<W>, H: MutableGraph<uint>>(graph: &G) -> Components<H> {
+    let vertices = graph.vertices();
+    let adj: Vec<Vec<uint>> = range(0, vertices).map(|v| graph.neighbors(v)).collect();
+    let mut transpose: Vec<Vec<uint>> = Vec::from_fn(vertices, |_| Vec::new());
+    for u in range(0, vertices) {
+        for &v in adj.get(u).iter() {
+            transpose.get_mut(v).push(u);
+        }
+    }
+    let mut visited = Vec::from_elem(vertices, false);
+    let mut position = Vec::from_elem(vertices, 0u);
+    let mut finished = Vec::with_capacity(vertices);
+    for root in range(0, vertices) {
+        if *visited.get(root) {
+            continue;
+        }
+        *visited.get_mut(root) = true;
+        let mut calls = vec!(root);
+        while !calls.is_empty() {
+            let u = *calls.last().unwrap();
+            let i = *position.get(u);
+            if i < adj.get(u).len() {
+                *position.get_mut(u) = i + 1;
+                let v = *adj.get(u).get(i);
+                if !*visited.get(v) {
+                    *visited.get_mut(v) = true;
+                    calls.push(v);
+                }
+            } else {
+                calls.pop();
+                finished.push(u);
+            }
+        }
+    }
+    let mut component: Vec<Option<uint>> = Vec::from_elem(vertices, None);
+    let mut count = 0u;
+    for &root in finished.iter().rev() {
+        if component.get(root).is_some() {
+            continue;
+        }
+        *component.get_mut(root) = Some(count);
+        let mut stack = vec!(root);
+        while !stack.is_empty() {
+            let u = stack.pop().unwrap();
+            for &v in transpose.get(u).iter() {
+                if component.get(v).is_none() {
+                    *component.get_mut(v) = Some(count);
+                    stack.push(v);
+                }
+            }
+        }
+        count += 1;
+    }
+    condense(graph, component.iter().map(|&c| c.unwrap()).collect(), count)
+}
+
+fn condense<W, G: Graph<W>, H: MutableGraph<uint>>(graph: &G, component: Vec<uint>,
+                                                    count: uint) -> Components<H> {
+    let mut condensation: ~H = MutableGraph::new(count);
+    for u in range(0, graph.vertices()) {
+        for &v in graph.neighbors(u).iter() {
+            let (from, to) = (*component.get(u), *component.get(v));
+            if from != to {
+                condensation.add(from, to, 1).unwrap();
+            }
+        }
+    }
+    Components { component: component, count: count, condensation: condensation }
+}
+
+/*
+ *  Graph:
+ *  V 0 1 2 3 4 5
+ *  0 0 1 0 0 0 0
+ *  1 0 0 1 0 0 0
+ *  2 1 0 0 1 0 0
+ *  3 0 0 0 0 1 0
+ *  4 0 0 0 1 0 1
+ *  5 0 0 0 0 0 0
+ *
+ */
+#[cfg(test)]
+fn component_graph() -> ~AdjacencyMatrix<int> {
+    let mut graph: ~AdjacencyMatrix<int> = MutableGraph::new(6);
+    let edges = [(0u,1u), (1,2), (2,0), (2,3), (3,4), (4,3), (4,5)];
+    for &(x, y) in edges.iter() {
+        assert!(graph.add(x, y, 1).is_ok());
+    }
+    graph
+}
+
+#[test]
+fn test_tarjan() {
+    let graph = component_graph();
+    let components: Components<AdjacencyMatrix<uint>> = tarjan(&*graph);
+    assert_eq!(components.count, 3);
+    assert_eq!(components.component, vec!(0u, 0, 0, 1, 1, 2));
+    assert_eq!(components.condensation.neighbors(0), vec!(1u));
+    assert_eq!(components.condensation.neighbors(1), vec!(2u));
+    assert!(components.condensation.neighbors(2).is_empty());
+    assert!(is_dag(&*components.condensation));
+}
+
+#[test]
+fn test_kosaraju_matches_tarjan() {
+    let mut graph = component_graph();
+    assert!(graph.add(5, 5, 1).is_ok());
+    let by_kosaraju: Components<AdjacencyList<uint>> = kosaraju(&*graph);
+    let by_tarjan: Components<AdjacencyList<uint>> = tarjan(&*graph);
+    assert_eq!(by_kosaraju.count, 3);
+    assert_eq!(by_kosaraju.component, by_tarjan.component);
+    for c in range(0u, 3) {
+        assert_eq!(by_kosaraju.condensation.neighbors(c), by_tarjan.condensation.neighbors(c));
+    }
+}
@@ -0,0 +1,650 @@
+/*
+ * Christopher Piraino
+ *
+ * Shortest path algorithms that work
+ * over any implementation of the Graph
+ * trait.
+ */
+use std::vec::Vec;
+use std::result::Result;
+use std::num::Zero;
+use graph::{Graph, Matrix, VectorMatrix, Weight, check_bounds, improves};
+use f_heap::{FHeap, FibEntry};
+#[test]
+use graph::{MutableGraph, AdjacencyMatrix};
+
+// Reasons a shortest path search can fail.
+#[deriving(Eq, Show)]
+pub enum PathError {
+    // Bad input, such as an out of bounds vertex or a negative edge for Dijkstra.
+    InvalidInput(~str),
+    // A cycle with negative total value, as the list of vertices along it.
+    NegativeCycle(Vec<uint>)
+}
+
+// The result of a single-source search. Vertices that cannot be
+// reached from the source have neither a distance nor a predecessor.
+pub struct ShortestPaths<W> {
+    pub source: uint,
+    pub distance: Vec<Option<W>>,
+    pub predecessor: Vec<Option<uint>>
+}
+
+impl<W> ShortestPaths<W> {
+    // Follows the predecessor links back from target, returning the
+    // vertices on the path from the source in order.
+    pub fn path_to(&self, target: uint) -> Option<Vec<uint>> {
+        if target >= self.distance.len() || self.distance.get(target).is_none() {
+            return None;
+        }
+        let mut path = vec!(target);
+        let mut current = target;
+        while current != self.source {
+            current = (*self.predecessor.get(current)).unwrap();
+            path.push(current);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+// A single path found by a point to point search.
+pub struct Path<W> {
+    pub vertices: Vec<uint>,
+    pub cost: W
+}
+
+/*  The result of an all-pairs search. Entry (x, y) of distance is the length
+ *  of the shortest path from x to y, and entry (x, y) of next is the vertex
+ *  that follows x on that path. Both are None when y cannot be reached from x.
+ */
+pub struct AllPairs<W> {
+    pub distance: ~VectorMatrix<Option<W>>,
+    pub next: ~VectorMatrix<Option<uint>>
+}
+
+impl<W: Clone> AllPairs<W> {
+    pub fn cost(&self, x: uint, y: uint) -> Option<W> {
+        match self.distance.at(x, y) {
+            Ok(dist) => dist,
+            Err(_) => None
+        }
+    }
+    // Follows the next hops from x to y, returning every vertex on the path.
+    pub fn path(&self, x: uint, y: uint) -> Option<Vec<uint>> {
+        if self.cost(x, y).is_none() {
+            return None;
+        }
+        let mut path = vec!(x);
+        let mut current = x;
+        while current != y {
+            current = self.next.at(current, y).unwrap().unwrap();
+            path.push(current);
+        }
+        Some(path)
+    }
+}
+
+fn check_vertex<W, G: Graph<W>>(graph: &G, v: uint) -> Result<(), PathError> {
+    check_bounds(graph.vertices(), v, v).map_err(|err| InvalidInput(err))
+}
+
+fn edge_value<W, G: Graph<W>>(graph: &G, x: uint, y: uint) -> Result<W, PathError> {
+    match graph.get_edge_value(x, y) {
+        Ok(Some(value)) => Ok(value),
+        Ok(None) => Err(InvalidInput(format!("({}, {}) is not an edge.", x, y))),
+        Err(err) => Err(InvalidInput(err))
+    }
+}
+
+// Every edge of the graph as a (source, target, value) triple.
+fn edge_list<W, G: Graph<W>>(graph: &G) -> Result<Vec<(uint, uint, W)>, PathError> {
+    let mut edges = Vec::new();
+    for u in range(0, graph.vertices()) {
+        for &v in graph.neighbors(u).iter() {
+            edges.push((u, v, try!(edge_value(graph, u, v))));
+        }
+    }
+    Ok(edges)
+}
+
+/*  Dijkstra's algorithm using the Fibonacci heap as the priority queue.
+ *  A vertex is inserted into the heap the first time it is discovered, and
+ *  decrease_key is used whenever a shorter path to it is found afterwards.
+ *  Edge values must be non-negative.
+ */
+pub fn dijkstra<W: Weight, G: Graph<W>>(graph: &G, source: uint)
+                                        -> Result<ShortestPaths<W>, PathError> {
+    dijkstra_with(graph, source, |u, v| edge_value(graph, u, v))
+}
+
+// Dijkstra's algorithm with the value of each edge given by weight.
+fn dijkstra_with<W: Weight, G: Graph<W>>(graph: &G, source: uint,
+                                         weight: |uint, uint| -> Result<W, PathError>)
+                                         -> Result<ShortestPaths<W>, PathError> {
+    try!(check_vertex(graph, source));
+    let vertices = graph.vertices();
+    let zero: W = Zero::zero();
+    let mut distance: Vec<Option<W>> = Vec::from_elem(vertices, None);
+    let mut predecessor: Vec<Option<uint>> = Vec::from_elem(vertices, None);
+    // Heap entries of the vertices that have been discovered but not yet settled.
+    let mut entries: Vec<Option<FibEntry<W, uint>>> = Vec::from_elem(vertices, None);
+    let mut heap = FHeap::new();
+    *distance.get_mut(source) = Some(zero.clone());
+    *entries.get_mut(source) = Some(heap.insert(zero.clone(), source));
+    while !heap.is_empty() {
+        let (dist, u) = heap.delete_min();
+        *entries.get_mut(u) = None;
+        for &v in graph.neighbors(u).iter() {
+            let value = try!(weight(u, v));
+            if value < zero {
+                return Err(InvalidInput(format!("Edge ({}, {}) has negative value {}.",
+                                                u, v, value)));
+            }
+            let candidate = dist + value;
+            match *distance.get(v) {
+                Some(ref old) if *old <= candidate => continue,
+                Some(ref old) => heap.decrease_key(entries.get(v).clone().unwrap(),
+                                                   *old - candidate),
+                None => *entries.get_mut(v) = Some(heap.insert(candidate.clone(), v))
+            }
+            *distance.get_mut(v) = Some(candidate);
+            *predecessor.get_mut(v) = Some(u);
+        }
+    }
+    Ok(ShortestPaths { source: source, distance: distance, predecessor: predecessor })
+}
+
+/*  Bellman-Ford, which allows negative edge values. Every edge is relaxed
+ *  once per round; after vertices - 1 rounds all shortest paths are known,
+ *  so any edge that can still be relaxed in the next round means that a
+ *  negative cycle is reachable from the source. Negative cycles that the
+ *  source cannot reach are ignored.
+ */
+pub fn bellman_ford<W: Weight, G: Graph<W>>(graph: &G, source: uint)
+                                            -> Result<ShortestPaths<W>, PathError> {
+    try!(check_vertex(graph, source));
+    let vertices = graph.vertices();
+    let edges = try!(edge_list(graph));
+    let mut distance: Vec<Option<W>> = Vec::from_elem(vertices, None);
+    let mut predecessor: Vec<Option<uint>> = Vec::from_elem(vertices, None);
+    *distance.get_mut(source) = Some(Zero::zero());
+    match relax_rounds(&edges, &mut distance, &mut predecessor) {
+        Some(v) => Err(NegativeCycle(negative_cycle(&predecessor, v))),
+        None => Ok(ShortestPaths { source: source, distance: distance, predecessor: predecessor })
+    }
+}
+
+// Runs the rounds of Bellman-Ford, returning a vertex that was still
+// relaxed in the final round if there is a negative cycle.
+fn relax_rounds<W: Weight>(edges: &Vec<(uint, uint, W)>, distance: &mut Vec<Option<W>>,
+                           predecessor: &mut Vec<Option<uint>>) -> Option<uint> {
+    // The last vertex relaxed in the current round, if any.
+    let mut relaxed = None;
+    for _ in range(0, distance.len()) {
+        relaxed = None;
+        for &(u, v, ref weight) in edges.iter() {
+            let candidate = match *distance.get(u) {
+                Some(ref dist) => *dist + *weight,
+                None => continue
+            };
+            if !improves(&candidate, distance.get(v)) {
+                continue;
+            }
+            *distance.get_mut(v) = Some(candidate);
+            *predecessor.get_mut(v) = Some(u);
+            relaxed = Some(v);
+        }
+        if relaxed.is_none() {
+            break;
+        }
+    }
+    relaxed
+}
+
+/*  A* search from source to goal. The heuristic estimates the cost of the
+ *  remaining path from a vertex to the goal; as long as it never overestimates,
+ *  the path found is a shortest one. The Fibonacci heap holds the open set keyed
+ *  by cost so far plus the estimate, and decrease_key is used when a cheaper way
+ *  to an open vertex is found. Returns None if the goal cannot be reached.
+ */
+pub fn astar<W: Weight, G: Graph<W>>(graph: &G, source: uint, goal: uint,
+                                     heuristic: |uint| -> W)
+                                     -> Result<Option<Path<W>>, PathError> {
+    try!(check_vertex(graph, source));
+    try!(check_vertex(graph, goal));
+    let vertices = graph.vertices();
+    let zero: W = Zero::zero();
+    let mut cost: Vec<Option<W>> = Vec::from_elem(vertices, None);
+    let mut predecessor: Vec<Option<uint>> = Vec::from_elem(vertices, None);
+    // Heap entries of the vertices in the open set.
+    let mut entries: Vec<Option<FibEntry<W, uint>>> = Vec::from_elem(vertices, None);
+    let mut heap = FHeap::new();
+    *cost.get_mut(source) = Some(zero.clone());
+    *entries.get_mut(source) = Some(heap.insert(heuristic(source), source));
+    while !heap.is_empty() {
+        let (_, u) = heap.delete_min();
+        *entries.get_mut(u) = None;
+        if u == goal {
+            let paths = ShortestPaths { source: source, distance: cost, predecessor: predecessor };
+            return Ok(Some(Path { vertices: paths.path_to(goal).unwrap(),
+                                  cost: paths.distance.get(goal).clone().unwrap() }));
+        }
+        let so_far = cost.get(u).clone().unwrap();
+        for &v in graph.neighbors(u).iter() {
+            let value = try!(edge_value(graph, u, v));
+            if value < zero {
+                return Err(InvalidInput(format!("Edge ({}, {}) has negative value {}.",
+                                                u, v, value)));
+            }
+            let candidate = so_far + value;
+            let old = cost.get(v).clone();
+            if !improves(&candidate, &old) {
+                continue;
+            }
+            // A closed vertex is reopened if the heuristic was not consistent.
+            let entry = *entries.get(v);
+            match entry {
+                Some(entry) => heap.decrease_key(entry, old.unwrap() - candidate),
+                None => *entries.get_mut(v) = Some(heap.insert(candidate + heuristic(v), v))
+            }
+            *cost.get_mut(v) = Some(candidate);
+            *predecessor.get_mut(v) = Some(u);
+        }
+    }
+    Ok(None)
+}
+
+/*  Floyd-Warshall. Each round allows one more vertex to be used as an
+ *  intermediate step on the paths between every pair of vertices. A negative
+ *  entry on the diagonal means a vertex lies on a negative cycle, which is then
+ *  recovered by running Bellman-Ford from that vertex.
+ */
+pub fn floyd_warshall<W: Weight, G: Graph<W>>(graph: &G) -> Result<AllPairs<W>, PathError> {
+    let vertices = graph.vertices();
+    let zero: W = Zero::zero();
+    let mut distance: ~VectorMatrix<Option<W>> = Matrix::zero(vertices, vertices);
+    let mut next: ~VectorMatrix<Option<uint>> = Matrix::zero(vertices, vertices);
+    for x in range(0, vertices) {
+        distance.set(x, x, Some(zero.clone())).unwrap();
+        next.set(x, x, Some(x)).unwrap();
+    }
+    for &(u, v, ref weight) in try!(edge_list(graph)).iter() {
+        if improves(weight, &distance.at(u, v).unwrap()) {
+            distance.set(u, v, Some(weight.clone())).unwrap();
+            next.set(u, v, Some(v)).unwrap();
+        }
+    }
+    for k in range(0, vertices) {
+        for x in range(0, vertices) {
+            let to_k = match distance.at(x, k).unwrap() {
+                Some(dist) => dist,
+                None => continue
+            };
+            for y in range(0, vertices) {
+                let through_k = match distance.at(k, y).unwrap() {
+                    Some(from_k) => to_k + from_k,
+                    None => continue
+                };
+                if improves(&through_k, &distance.at(x, y).unwrap()) {
+                    distance.set(x, y, Some(through_k)).unwrap();
+                    let hop = next.at(x, k).unwrap();
+                    next.set(x, y, hop).unwrap();
+                }
+            }
+        }
+        // Stop as soon as a negative cycle appears, before the values run away.
+        for x in range(0, vertices) {
+            if distance.at(x, x).unwrap().unwrap() < zero {
+                return match bellman_ford(graph, x) {
+                    Err(err) => Err(err),
+                    Ok(_) => fail!("Bellman-Ford found no negative cycle through {}.", x)
+                };
+            }
+        }
+    }
+    Ok(AllPairs { distance: distance, next: next })
+}
+
+/*  Johnson's algorithm, for sparse graphs with negative edge values. Bellman-Ford
+ *  from a virtual source joined to every vertex by an edge of value 0 gives each
+ *  vertex a potential h. Changing the value of every edge (u, v) to
+ *  value + h(u) - h(v) makes all of them non-negative without changing which
+ *  paths are shortest, so Dijkstra can then be run from every vertex.
+ */
+pub fn johnson<W: Weight, G: Graph<W>>(graph: &G) -> Result<AllPairs<W>, PathError> {
+    let vertices = graph.vertices();
+    let edges = try!(edge_list(graph));
+    // Every vertex starting at 0 stands in for the edges from the virtual source.
+    let mut potential: Vec<Option<W>> = Vec::from_elem(vertices, Some(Zero::zero()));
+    let mut predecessor: Vec<Option<uint>> = Vec::from_elem(vertices, None);
+    match relax_rounds(&edges, &mut potential, &mut predecessor) {
+        Some(v) => return Err(NegativeCycle(negative_cycle(&predecessor, v))),
+        None => {}
+    }
+    let h: Vec<W> = potential.move_iter().map(|p| p.unwrap()).collect();
+    let mut distance: ~VectorMatrix<Option<W>> = Matrix::zero(vertices, vertices);
+    let mut next: ~VectorMatrix<Option<uint>> = Matrix::zero(vertices, vertices);
+    for x in range(0, vertices) {
+        let paths = try!(dijkstra_with(graph, x, |u, v| {
+            edge_value(graph, u, v).map(|value| value + *h.get(u) - *h.get(v))
+        }));
+        let hops = first_hops(&paths);
+        for y in range(0, vertices) {
+            match *paths.distance.get(y) {
+                Some(ref dist) => {
+                    distance.set(x, y, Some(*dist - *h.get(x) + *h.get(y))).unwrap();
+                    next.set(x, y, *hops.get(y)).unwrap();
+                }
+                None => {}
+            }
+        }
+    }
+    Ok(AllPairs { distance: distance, next: next })
+}
+
+/*  The vertex that follows the source on the path to each reachable vertex,
+ *  or the source itself for the source. The hop of y is y when the source
+ *  precedes it and the hop of its predecessor otherwise, so each hop is worked
+ *  out once and shared by every path through it, taking O(V) time in all.
+ */
+fn first_hops<W>(paths: &ShortestPaths<W>) -> Vec<Option<uint>> {
+    let mut hop: Vec<Option<uint>> = Vec::from_elem(paths.distance.len(), None);
+    *hop.get_mut(paths.source) = Some(paths.source);
+    for target in range(0, paths.distance.len()) {
+        if paths.distance.get(target).is_none() {
+            continue;
+        }
+        // Walk back to the first vertex with a known hop, then fill in the
+        // vertices passed on the way, nearest to the source first.
+        let mut unknown = Vec::new();
+        let mut current = target;
+        while hop.get(current).is_none() {
+            unknown.push(current);
+            current = (*paths.predecessor.get(current)).unwrap();
+        }
+        for &v in unknown.iter().rev() {
+            let parent = (*paths.predecessor.get(v)).unwrap();
+            let first = if parent == paths.source { v } else { (*hop.get(parent)).unwrap() };
+            *hop.get_mut(v) = Some(first);
+        }
+    }
+    hop
+}
+
+// Walks back far enough from a vertex relaxed in the last round of
+// Bellman-Ford to be inside the cycle, then collects the cycle in edge order.
+fn negative_cycle(predecessor: &Vec<Option<uint>>, relaxed: uint) -> Vec<uint> {
+    let mut start = relaxed;
+    for _ in range(0, predecessor.len()) {
+        start = (*predecessor.get(start)).unwrap();
+    }
+    let mut cycle = vec!(start);
+    let mut current = (*predecessor.get(start)).unwrap();
+    while current != start {
+        cycle.push(current);
+        current = (*predecessor.get(current)).unwrap();
+    }
+    cycle.reverse();
+    cycle
+}
+
+/*
+ *  Graph:
+ *  V 0 1 2 3 4
+ *  0 0 4 1 0 0
+ *  1 0 0 0 1 0
+ *  2 0 2 0 5 0
+ *  3 0 0 0 0 0
+ *  4 0 0 0 0 0
+ *
+ */
+#[cfg(test)]
+fn weighted_graph() -> ~AdjacencyMatrix<int> {
+    let mut graph: ~AdjacencyMatrix<int> = MutableGraph::new(5);
+    assert!(graph.add(0,1,4).is_ok());
+    assert!(graph.add(0,2,1).is_ok());
+    assert!(graph.add(2,1,2).is_ok());
+    assert!(graph.add(1,3,1).is_ok());
+    assert!(graph.add(2,3,5).is_ok());
+    graph
+}
+
+#[test]
+fn test_dijkstra_distances() {
+    let graph = weighted_graph();
+    let paths = dijkstra(&*graph, 0).ok().unwrap();
+    assert_eq!(paths.distance, vec!(Some(0), Some(3), Some(1), Some(4), None));
+    assert_eq!(paths.predecessor, vec!(None, Some(2), Some(0), Some(1), None));
+}
+
+#[test]
+fn test_dijkstra_path_to() {
+    let graph = weighted_graph();
+    let paths = dijkstra(&*graph, 0).ok().unwrap();
+    assert_eq!(paths.path_to(3).unwrap(), vec!(0u, 2, 1, 3));
+    assert_eq!(paths.path_to(0).unwrap(), vec!(0u));
+    assert!(paths.path_to(4).is_none());
+}
+
+#[test]
+fn test_dijkstra_errors() {
+    let mut graph = weighted_graph();
+    assert!(dijkstra(&*graph, 5).is_err());
+    assert!(graph.add(3,4,-1).is_ok());
+    assert!(dijkstra(&*graph, 0).is_err());
+}
+
+/*
+ *  Graph:
+ *  V 0 1 2 3 4 5
+ *  0 0 4 5 0 0 0
+ *  1 0 0 0 2 0 0
+ *  2 0 -3 0 0 0 0
+ *  3 0 0 0 0 0 0
+ *  4 0 0 0 0 0 -1
+ *  5 0 0 0 0 -1 0
+ *
+ */
+#[cfg(test)]
+fn negative_graph() -> ~AdjacencyMatrix<int> {
+    let mut graph: ~AdjacencyMatrix<int> = MutableGraph::new(6);
+    assert!(graph.add(0,1,4).is_ok());
+    assert!(graph.add(0,2,5).is_ok());
+    assert!(graph.add(2,1,-3).is_ok());
+    assert!(graph.add(1,3,2).is_ok());
+    assert!(graph.add(4,5,-1).is_ok());
+    assert!(graph.add(5,4,-1).is_ok());
+    graph
+}
+
+#[test]
+fn test_bellman_ford_negative_edges() {
+    let graph = negative_graph();
+    let paths = bellman_ford(&*graph, 0).ok().unwrap();
+    assert_eq!(paths.distance, vec!(Some(0), Some(2), Some(5), Some(4), None, None));
+    assert_eq!(paths.path_to(3).unwrap(), vec!(0u, 2, 1, 3));
+    let matrix = weighted_graph();
+    assert_eq!(bellman_ford(&*matrix, 0).ok().unwrap().distance,
+               dijkstra(&*matrix, 0).ok().unwrap().distance);
+}
+
+#[test]
+fn test_bellman_ford_negative_cycle() {
+    let mut graph = negative_graph();
+    assert!(graph.add(3,2,-5).is_ok());
+    match bellman_ford(&*graph, 0) {
+        Err(NegativeCycle(cycle)) => {
+            assert_eq!(cycle.len(), 3);
+            for i in range(0, cycle.len()) {
+                let next = *cycle.get((i + 1) % cycle.len());
+                assert!(graph.adjacent(*cycle.get(i), next).ok().unwrap());
+            }
+            let mut sorted = cycle.clone();
+            sorted.as_mut_slice().sort();
+            assert_eq!(sorted, vec!(1u, 2, 3));
+        }
+        _ => fail!("Expected a negative cycle.")
+    }
+    assert!(bellman_ford(&*graph, 6).is_err());
+}
+
+#[test]
+fn test_floyd_warshall_matches_single_source() {
+    let mut graph = negative_graph();
+    // Floyd-Warshall rejects every negative cycle, reachable or not.
+    assert!(graph.delete(4,5).is_ok());
+    let all = floyd_warshall(&*graph).ok().unwrap();
+    for x in range(0u, 6) {
+        let paths = bellman_ford(&*graph, x).ok().unwrap();
+        for y in range(0u, 6) {
+            assert_eq!(all.cost(x, y), *paths.distance.get(y));
+        }
+    }
+    assert_eq!(all.path(0, 3).unwrap(), vec!(0u, 2, 1, 3));
+    assert_eq!(all.path(2, 2).unwrap(), vec!(2u));
+    assert!(all.path(3, 0).is_none());
+    assert_eq!(all.distance.at(3, 0).unwrap(), None);
+    assert_eq!(all.next.at(3, 0).unwrap(), None);
+}
+
+#[test]
+fn test_floyd_warshall_negative_cycle() {
+    let mut graph = negative_graph();
+    assert!(graph.delete(4,5).is_ok());
+    assert!(graph.add(3,2,-5).is_ok());
+    match floyd_warshall(&*graph) {
+        Err(NegativeCycle(cycle)) => assert_eq!(cycle.len(), 3),
+        _ => fail!("Expected a negative cycle.")
+    }
+}
+
+#[test]
+fn test_johnson_matches_floyd_warshall() {
+    let mut graph = negative_graph();
+    assert!(graph.delete(4,5).is_ok());
+    let fw = floyd_warshall(&*graph).ok().unwrap();
+    let all = johnson(&*graph).ok().unwrap();
+    for x in range(0u, 6) {
+        for y in range(0u, 6) {
+            assert_eq!(all.cost(x, y), fw.cost(x, y));
+            assert_eq!(all.path(x, y), fw.path(x, y));
+        }
+    }
+    assert_eq!(all.next.at(3, 0).unwrap(), None);
+}
+
+#[test]
+fn test_johnson_negative_cycle() {
+    // The cycle between 4 and 5 is unreachable from 0 but still rejected.
+    let graph = negative_graph();
+    match johnson(&*graph) {
+        Err(NegativeCycle(cycle)) => {
+            let mut sorted = cycle.clone();
+            sorted.as_mut_slice().sort();
+            assert_eq!(sorted, vec!(4u, 5));
+        }
+        _ => fail!("Expected a negative cycle.")
+    }
+}
+
+// A size by size grid where each cell is joined to the cells beside it.
+#[cfg(test)]
+fn grid_graph(size: uint) -> ~AdjacencyMatrix<int> {
+    let mut graph: ~AdjacencyMatrix<int> = MutableGraph::new(size * size);
+    for row in range(0, size) {
+        for col in range(0, size) {
+            let v = row * size + col;
+            if col + 1 < size {
+                assert!(graph.add(v, v + 1, 1).is_ok());
+                assert!(graph.add(v + 1, v, 1).is_ok());
+            }
+            if row + 1 < size {
+                assert!(graph.add(v, v + size, 1).is_ok());
+                assert!(graph.add(v + size, v, 1).is_ok());
+            }
+        }
+    }
+    graph
+}
+
+#[test]
+fn test_astar_grid() {
+    let mut graph = grid_graph(3);
+    // Wall off the center cell.
+    for &v in [1u, 3, 5, 7].iter() {
+        assert!(graph.delete(v, 4).is_ok());
+    }
+    let manhattan = |v: uint| ((2 - v / 3) + (2 - v % 3)) as int;
+    let path = astar(&*graph, 0, 8, manhattan).ok().unwrap().unwrap();
+    assert_eq!(path.cost, 4);
+    assert_eq!(path.vertices.len(), 5);
+    assert!(!path.vertices.contains(&4));
+    let blind = astar(&*graph, 0, 8, |_| 0).ok().unwrap().unwrap();
+    assert_eq!(blind.cost, (*dijkstra(&*graph, 0).ok().unwrap().distance.get(8)).unwrap());
+}
+
+#[test]
+fn test_astar_no_path() {
+    let mut graph = grid_graph(3);
+    assert!(graph.delete(5, 8).is_ok());
+    assert!(graph.delete(7, 8).is_ok());
+    assert!(astar(&*graph, 0, 8, |_| 0).ok().unwrap().is_none());
+    assert!(astar(&*graph, 0, 9, |_| 0).is_err());
+}
+
+#[test]
+fn test_dijkstra_float_weights() {
+    let mut graph: ~AdjacencyMatrix<f64> = MutableGraph::new(3);
+    assert!(graph.add(0,1,0.5).is_ok());
+    assert!(graph.add(1,2,0.25).is_ok());
+    assert!(graph.add(0,2,1.0).is_ok());
+    let paths = dijkstra(&*graph, 0).ok().unwrap();
+    assert_eq!(paths.distance, vec!(Some(0.0), Some(0.5), Some(0.75)));
+    assert_eq!(paths.path_to(2).unwrap(), vec!(0u, 1, 2));
+}
+
+// A cost compared by distance first and by the number of tolls paid after.
+#[cfg(test)]
+#[deriving(Clone, Eq, Ord, Show)]
+struct Toll {
+    distance: int,
+    tolls: int
+}
+
+#[cfg(test)]
+impl Add<Toll, Toll> for Toll {
+    fn add(&self, other: &Toll) -> Toll {
+        Toll { distance: self.distance + other.distance, tolls: self.tolls + other.tolls }
+    }
+}
+
+#[cfg(test)]
+impl Sub<Toll, Toll> for Toll {
+    fn sub(&self, other: &Toll) -> Toll {
+        Toll { distance: self.distance - other.distance, tolls: self.tolls - other.tolls }
+    }
+}
+
+#[cfg(test)]
+impl Zero for Toll {
+    fn zero() -> Toll {
+        Toll { distance: 0, tolls: 0 }
+    }
+    fn is_zero(&self) -> bool {
+        self.distance == 0 && self.tolls == 0
+    }
+}
+
+#[test]
+fn test_dijkstra_custom_weights() {
+    let mut graph: ~AdjacencyMatrix<Toll> = MutableGraph::new(4);
+    assert!(graph.add(0, 1, Toll { distance: 2, tolls: 1 }).is_ok());
+    assert!(graph.add(0, 2, Toll { distance: 1, tolls: 0 }).is_ok());
+    assert!(graph.add(2, 1, Toll { distance: 1, tolls: 0 }).is_ok());
+    assert!(graph.add(1, 3, Toll { distance: 1, tolls: 0 }).is_ok());
+    let paths = dijkstra(&*graph, 0).ok().unwrap();
+    assert_eq!(paths.path_to(3).unwrap(), vec!(0u, 2, 1, 3));
+    assert_eq!(*paths.distance.get(3), Some(Toll { distance: 3, tolls: 0 }));
+    assert_eq!(bellman_ford(&*graph, 0).ok().unwrap().distance, paths.distance);
+}
@@ -0,0 +1,417 @@
+/*
+ * Christopher Piraino
+ *
+ * Graph traversals that report more than
+ * the order the vertices are visited in.
+ */
+extern crate collections = "collections#0.11-pre";
+use collections::ringbuf::RingBuf;
+use collections::deque::Deque;
+use std::vec::Vec;
+use graph::{Graph, Control, Continue, Prune, Stop, check_bounds};
+#[test]
+use graph::{MutableGraph, AdjacencyMatrix};
+
+/*  Callbacks made by depth_first_visit. Every edge out of a discovered vertex
+ *  is classified exactly once: a tree edge leads to a new vertex, a back edge
+ *  leads to a vertex that has not finished yet (so it closes a cycle), and any
+ *  other edge leads to a finished vertex. Each callback does nothing and
+ *  returns Continue unless overridden. Returning Stop from any of them ends
+ *  the search there, leaving the vertices still being explored unfinished.
+ *  Returning Prune from discover_vertex finishes the vertex without following
+ *  its edges, and from tree_edge skips the edge; elsewhere it is the same as
+ *  Continue.
+ */
+pub trait DfsVisitor<G> {
+    fn discover_vertex(&mut self, _graph: &G, _v: uint, _time: uint) -> Control { Continue }
+    fn finish_vertex(&mut self, _graph: &G, _v: uint, _time: uint) -> Control { Continue }
+    fn tree_edge(&mut self, _graph: &G, _u: uint, _v: uint) -> Control { Continue }
+    fn back_edge(&mut self, _graph: &G, _u: uint, _v: uint) -> Control { Continue }
+    fn forward_or_cross_edge(&mut self, _graph: &G, _u: uint, _v: uint) -> Control { Continue }
+}
+
+/*  The times at which each vertex was discovered and finished. A single
+ *  clock is advanced by both events, so the interval of a descendant always
+ *  lies inside the interval of its ancestor.
+ */
+pub struct DfsTimes {
+    pub discovered: Vec<Option<uint>>,
+    pub finished: Vec<Option<uint>>
+}
+
+// Visits every vertex reachable from start in depth first order.
+pub fn depth_first_visit<W, G: Graph<W>, V: DfsVisitor<G>>(graph: &G, visitor: &mut V,
+                                                           start: uint) -> DfsTimes {
+    let mut search = DepthFirst::new(graph);
+    search.visit(visitor, start);
+    search.times
+}
+
+// Visits every vertex, starting a new tree from each vertex not yet discovered.
+pub fn depth_first_visit_all<W, G: Graph<W>, V: DfsVisitor<G>>(graph: &G, visitor: &mut V)
+                                                               -> DfsTimes {
+    let mut search = DepthFirst::new(graph);
+    for root in range(0, graph.vertices()) {
+        if search.times.discovered.get(root).is_none() && search.visit(visitor, root) {
+            break;
+        }
+    }
+    search.times
+}
+
+// The state of a depth first search, shared between the trees of a forest.
+struct DepthFirst<'a, W, G> {
+    graph: &'a G,
+    times: DfsTimes,
+    clock: uint,
+    // Neighbors of each discovered vertex, and the next one to explore.
+    adj: Vec<Vec<uint>>,
+    position: Vec<uint>
+}
+
+impl<'a, W, G: Graph<W>> DepthFirst<'a, W, G> {
+    fn new(graph: &'a G) -> DepthFirst<'a, W, G> {
+        let vertices = graph.vertices();
+        DepthFirst {
+            graph: graph,
+            times: DfsTimes {
+                discovered: Vec::from_elem(vertices, None),
+                finished: Vec::from_elem(vertices, None)
+            },
+            clock: 0,
+            adj: Vec::from_fn(vertices, |_| Vec::new()),
+            position: Vec::from_elem(vertices, 0u)
+        }
+    }
+    // A pruned vertex is left with no neighbors to explore.
+    fn discover<V: DfsVisitor<G>>(&mut self, visitor: &mut V, v: uint) -> Control {
+        *self.times.discovered.get_mut(v) = Some(self.clock);
+        let control = visitor.discover_vertex(self.graph, v, self.clock);
+        self.clock += 1;
+        if control != Prune {
+            *self.adj.get_mut(v) = self.graph.neighbors(v);
+        }
+        control
+    }
+    // Returns true if the visitor stopped the search.
+    fn visit<V: DfsVisitor<G>>(&mut self, visitor: &mut V, root: uint) -> bool {
+        if self.discover(visitor, root) == Stop {
+            return true;
+        }
+        let mut stack = vec!(root);
+        while !stack.is_empty() {
+            let u = *stack.last().unwrap();
+            let i = *self.position.get(u);
+            if i == self.adj.get(u).len() {
+                stack.pop();
+                *self.times.finished.get_mut(u) = Some(self.clock);
+                let control = visitor.finish_vertex(self.graph, u, self.clock);
+                self.clock += 1;
+                if control == Stop {
+                    return true;
+                }
+                continue;
+            }
+            *self.position.get_mut(u) = i + 1;
+            let v = *self.adj.get(u).get(i);
+            let control = if self.times.discovered.get(v).is_none() {
+                match visitor.tree_edge(self.graph, u, v) {
+                    Continue => {
+                        stack.push(v);
+                        self.discover(visitor, v)
+                    }
+                    control => control
+                }
+            } else if self.times.finished.get(v).is_none() {
+                visitor.back_edge(self.graph, u, v)
+            } else {
+                visitor.forward_or_cross_edge(self.graph, u, v)
+            };
+            if control == Stop {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/*  A search of the whole graph, restarted from the lowest numbered vertex not
+ *  yet visited each time the previous tree is exhausted. Trees are numbered in
+ *  the order they were started.
+ */
+pub struct Forest {
+    // Every vertex in the order it was visited.
+    pub order: Vec<uint>,
+    // The tree each vertex belongs to.
+    pub tree: Vec<uint>,
+    // The vertex each tree was started from.
+    pub roots: Vec<uint>
+}
+
+impl Forest {
+    fn new(vertices: uint) -> Forest {
+        Forest {
+            order: Vec::with_capacity(vertices),
+            tree: Vec::from_elem(vertices, 0u),
+            roots: Vec::new()
+        }
+    }
+}
+
+// Depth first search of every vertex, visiting each tree in the same order as
+// depth_first_search would from its root.
+pub fn depth_first_forest<W, G: Graph<W>>(graph: &G) -> Forest {
+    let vertices = graph.vertices();
+    let mut forest = Forest::new(vertices);
+    let mut visited = Vec::from_elem(vertices, false);
+    let mut stack = Vec::new();
+    for root in range(0, vertices) {
+        if *visited.get(root) {
+            continue;
+        }
+        let id = forest.roots.len();
+        forest.roots.push(root);
+        stack.push(root);
+        while !stack.is_empty() {
+            let current = stack.pop().unwrap();
+            if *visited.get(current) {
+                continue;
+            }
+            *visited.get_mut(current) = true;
+            *forest.tree.get_mut(current) = id;
+            forest.order.push(current);
+            for &x in graph.neighbors(current).iter() {
+                stack.push(x);
+            }
+        }
+    }
+    forest
+}
+
+// Breadth first search of every vertex, visiting each tree in the same order as
+// breadth_first_search would from its root.
+pub fn breadth_first_forest<W, G: Graph<W>>(graph: &G) -> Forest {
+    let vertices = graph.vertices();
+    let mut forest = Forest::new(vertices);
+    let mut visited = Vec::from_elem(vertices, false);
+    let mut queue = RingBuf::new();
+    for root in range(0, vertices) {
+        if *visited.get(root) {
+            continue;
+        }
+        let id = forest.roots.len();
+        forest.roots.push(root);
+        *visited.get_mut(root) = true;
+        queue.push_back(root);
+        while !queue.is_empty() {
+            let current = queue.pop_front().unwrap();
+            *forest.tree.get_mut(current) = id;
+            forest.order.push(current);
+            for &x in graph.neighbors(current).iter() {
+                if !*visited.get(x) {
+                    *visited.get_mut(x) = true;
+                    queue.push_back(x);
+                }
+            }
+        }
+    }
+    forest
+}
+
+/*  The connected components of an undirected graph, where every edge is
+ *  stored in both directions. Each component is a sorted list of its vertices,
+ *  and the components are ordered by their smallest vertex.
+ */
+pub fn connected_components<W, G: Graph<W>>(graph: &G) -> Vec<Vec<uint>> {
+    let forest = breadth_first_forest(graph);
+    let mut components: Vec<Vec<uint>> = Vec::from_fn(forest.roots.len(), |_| Vec::new());
+    for v in range(0, graph.vertices()) {
+        components.get_mut(*forest.tree.get(v)).push(v);
+    }
+    components
+}
+
+/*  The result of a breadth first search started from several sources at once.
+ *  Every reachable vertex gets its hop distance to the nearest source and which
+ *  source that is, with ties going to the source listed first.
+ */
+pub struct Layers {
+    pub distance: Vec<Option<uint>>,
+    pub source: Vec<Option<uint>>,
+    // The vertices at each distance, in the order they were reached.
+    pub layers: Vec<Vec<uint>>
+}
+
+// Runs bfs_iter_from the sources. A vertex takes the source of its parent,
+// which is always yielded before it.
+pub fn multi_source_bfs<W, G: Graph<W>>(graph: &G, sources: &[uint]) -> Result<Layers, ~str> {
+    let vertices = graph.vertices();
+    for &s in sources.iter() {
+        try!(check_bounds(vertices, s, s));
+    }
+    let mut distance: Vec<Option<uint>> = Vec::from_elem(vertices, None);
+    let mut source: Vec<Option<uint>> = Vec::from_elem(vertices, None);
+    let mut layers: Vec<Vec<uint>> = Vec::new();
+    for (v, depth, parent) in graph.bfs_iter_from(sources) {
+        if depth == layers.len() {
+            layers.push(Vec::new());
+        }
+        layers.get_mut(depth).push(v);
+        *distance.get_mut(v) = Some(depth);
+        *source.get_mut(v) = match parent {
+            Some(p) => *source.get(p),
+            None => Some(v)
+        };
+    }
+    Ok(Layers { distance: distance, source: source, layers: layers })
+}
+
+// Records every event, answering one chosen event with the given control.
+#[cfg(test)]
+struct Recorder {
+    events: Vec<~str>,
+    answer: Option<(~str, Control)>
+}
+
+#[cfg(test)]
+impl Recorder {
+    fn new() -> Recorder {
+        Recorder { events: Vec::new(), answer: None }
+    }
+    fn record(&mut self, event: ~str) -> Control {
+        let control = match self.answer {
+            Some((ref on, control)) if *on == event => control,
+            _ => Continue
+        };
+        self.events.push(event);
+        control
+    }
+}
+
+#[cfg(test)]
+impl<G> DfsVisitor<G> for Recorder {
+    fn discover_vertex(&mut self, _: &G, v: uint, time: uint) -> Control {
+        self.record(format!("discover {} at {}", v, time))
+    }
+    fn finish_vertex(&mut self, _: &G, v: uint, time: uint) -> Control {
+        self.record(format!("finish {} at {}", v, time))
+    }
+    fn tree_edge(&mut self, _: &G, u: uint, v: uint) -> Control {
+        self.record(format!("tree {} {}", u, v))
+    }
+    fn back_edge(&mut self, _: &G, u: uint, v: uint) -> Control {
+        self.record(format!("back {} {}", u, v))
+    }
+    fn forward_or_cross_edge(&mut self, _: &G, u: uint, v: uint) -> Control {
+        self.record(format!("forward or cross {} {}", u, v))
+    }
+}
+
+/*
+ *  Graph:
+ *  V 0 1 2 3
+ *  0 0 1 1 0
+ *  1 0 0 1 0
+ *  2 1 0 0 0
+ *  3 0 1 0 0
+ *
+ */
+#[cfg(test)]
+fn classified_graph() -> ~AdjacencyMatrix<int> {
+    let mut graph: ~AdjacencyMatrix<int> = MutableGraph::new(4);
+    let edges = [(0u,1u), (0,2), (1,2), (2,0), (3,1)];
+    for &(x, y) in edges.iter() {
+        assert!(graph.add(x, y, 1).is_ok());
+    }
+    graph
+}
+
+#[test]
+fn test_depth_first_visit_all() {
+    let graph = classified_graph();
+    let mut recorder = Recorder::new();
+    let times = depth_first_visit_all(&*graph, &mut recorder);
+    assert_eq!(recorder.events, vec!(~"discover 0 at 0", ~"tree 0 1", ~"discover 1 at 1",
+                                     ~"tree 1 2", ~"discover 2 at 2", ~"back 2 0",
+                                     ~"finish 2 at 3", ~"finish 1 at 4",
+                                     ~"forward or cross 0 2", ~"finish 0 at 5",
+                                     ~"discover 3 at 6", ~"forward or cross 3 1",
+                                     ~"finish 3 at 7"));
+    assert_eq!(times.discovered, vec!(Some(0u), Some(1), Some(2), Some(6)));
+    assert_eq!(times.finished, vec!(Some(5u), Some(4), Some(3), Some(7)));
+}
+
+#[test]
+fn test_depth_first_visit_reachable_only() {
+    let graph = classified_graph();
+    let mut recorder = Recorder::new();
+    let times = depth_first_visit(&*graph, &mut recorder, 1);
+    assert_eq!(times.discovered, vec!(Some(2u), Some(0), Some(1), None));
+    assert_eq!(times.finished, vec!(Some(3u), Some(5), Some(4), None));
+    assert!(recorder.events.contains(&~"back 0 1"));
+}
+
+#[test]
+fn test_depth_first_visit_stop_and_prune() {
+    let graph = classified_graph();
+    let mut recorder = Recorder::new();
+    recorder.answer = Some((~"back 2 0", Stop));
+    let times = depth_first_visit_all(&*graph, &mut recorder);
+    assert_eq!(recorder.events, vec!(~"discover 0 at 0", ~"tree 0 1", ~"discover 1 at 1",
+                                     ~"tree 1 2", ~"discover 2 at 2", ~"back 2 0"));
+    assert_eq!(times.discovered, vec!(Some(0u), Some(1), Some(2), None));
+    assert_eq!(times.finished, vec!(None, None, None, None));
+    let mut recorder = Recorder::new();
+    recorder.answer = Some((~"discover 1 at 1", Prune));
+    let times = depth_first_visit(&*graph, &mut recorder, 0);
+    assert_eq!(recorder.events, vec!(~"discover 0 at 0", ~"tree 0 1", ~"discover 1 at 1",
+                                     ~"finish 1 at 2", ~"tree 0 2", ~"discover 2 at 3",
+                                     ~"back 2 0", ~"finish 2 at 4", ~"finish 0 at 5"));
+    assert_eq!(times.finished, vec!(Some(5u), Some(2), Some(4), None));
+}
+
+#[test]
+fn test_forests() {
+    let mut graph: ~AdjacencyMatrix<int> = MutableGraph::new(6);
+    let edges = [(0u,2u), (2,0), (1,3), (3,4), (4,1), (5,4)];
+    for &(x, y) in edges.iter() {
+        assert!(graph.add(x, y, 1).is_ok());
+    }
+    let dfs = depth_first_forest(&*graph);
+    assert_eq!(dfs.order, vec!(0u, 2, 1, 3, 4, 5));
+    assert_eq!(dfs.tree, vec!(0u, 1, 0, 1, 1, 2));
+    assert_eq!(dfs.roots, vec!(0u, 1, 5));
+    let bfs = breadth_first_forest(&*graph);
+    assert_eq!(bfs.order, vec!(0u, 2, 1, 3, 4, 5));
+    assert_eq!(bfs.tree, dfs.tree);
+    assert_eq!(bfs.roots, dfs.roots);
+}
+
+#[test]
+fn test_connected_components() {
+    let mut graph: ~AdjacencyMatrix<int> = MutableGraph::new(7);
+    let edges = [(0u,4u), (4,6), (1,5), (2,2)];
+    for &(x, y) in edges.iter() {
+        assert!(graph.add(x, y, 1).is_ok());
+        assert!(graph.add(y, x, 1).is_ok());
+    }
+    assert_eq!(connected_components(&*graph),
+               vec!(vec!(0u, 4, 6), vec!(1u, 5), vec!(2u), vec!(3u)));
+}
+
+#[test]
+fn test_multi_source_bfs() {
+    // A path 0 - 1 - 2 - 3 - 4 - 5 - 6 with 7 off on its own.
+    let mut graph: ~AdjacencyMatrix<int> = MutableGraph::new(8);
+    for v in range(0u, 6) {
+        assert!(graph.add(v, v + 1, 1).is_ok());
+        assert!(graph.add(v + 1, v, 1).is_ok());
+    }
+    let result = multi_source_bfs(&*graph, &[5u, 1, 1]).ok().unwrap();
+    assert_eq!(result.distance, vec!(Some(1u), Some(0), Some(1), Some(2),
+                                     Some(1), Some(0), Some(1), None));
+    assert_eq!(result.source, vec!(Some(1u), Some(1), Some(1), Some(5),
+                                   Some(5), Some(5), Some(5), None));
+    assert_eq!(result.layers, vec!(vec!(5u, 1), vec!(4u, 6, 0, 2), vec!(3u)));
+    assert!(multi_source_bfs(&*graph, &[2u, 8]).is_err());
+}
@@ -0,0 +1,132 @@
+/*
+ * Christopher Piraino
+ *
+ * An undirected graph, stored as the
+ * upper triangle of its adjacency matrix.
+ */
+use std::vec::Vec;
+use std::result::Result;
+use std::mem::replace;
+use graph::{Graph, MutableGraph, DynamicGraph, check_bounds, removal_remap};
+#[test]
+use traversal::connected_components;
+
+/*  Every edge joins x and y in both directions, so adding, deleting or
+ *  changing (x, y) does the same to (y, x). Only the entries with x <= y are
+ *  stored, packed one column after another, which takes about half the
+ *  memory of VectorMatrix and lets a new vertex be added as one more column.
+ */
+pub struct UndirectedMatrix<W> {
+    elements: Vec<Option<W>>,
+    vertices: uint
+}
+
+// Private methods on UndirectedMatrix.
+impl<W> UndirectedMatrix<W> {
+    // The position of the entry shared by (x, y) and (y, x).
+    fn index(&self, x: uint, y: uint) -> Result<uint, ~str> {
+        try!(check_bounds(self.vertices, x, y));
+        let (low, high) = if x <= y { (x, y) } else { (y, x) };
+        Ok(high * (high + 1) / 2 + low)
+    }
+}
+
+impl<W: Clone> Graph<W> for UndirectedMatrix<W> {
+    fn vertices(&self) -> uint {
+        self.vertices
+    }
+    fn adjacent(&self, x: uint, y: uint) -> Result<bool, ~str> {
+        let i = try!(self.index(x, y));
+        Ok(self.elements.get(i).is_some())
+    }
+    fn neighbors(&self, x: uint) -> Vec<uint> {
+        range(0, self.vertices).filter(|&y| {
+            self.elements.get(self.index(x, y).unwrap()).is_some()
+        }).collect()
+    }
+    fn get_edge_value(&self, x: uint, y: uint) -> Result<Option<W>, ~str> {
+        let i = try!(self.index(x, y));
+        Ok(self.elements.get(i).clone())
+    }
+}
+
+impl<W: Clone> MutableGraph<W> for UndirectedMatrix<W> {
+    fn new(vertices: uint) -> ~UndirectedMatrix<W> {
+        ~UndirectedMatrix {
+            elements: Vec::from_elem(vertices * (vertices + 1) / 2, None),
+            vertices: vertices
+        }
+    }
+    fn add(&mut self, x: uint, y: uint, val: W) -> Result<W, ~str> {
+        let i = try!(self.index(x, y));
+        *self.elements.get_mut(i) = Some(val.clone());
+        Ok(val)
+    }
+    fn delete(&mut self, x: uint, y: uint) -> Result<Option<W>, ~str> {
+        let i = try!(self.index(x, y));
+        Ok(replace(self.elements.get_mut(i), None))
+    }
+    fn set_edge_value(&mut self, x: uint, y: uint, val: W) -> Result<W, ~str> {
+        if !try!(self.adjacent(x, y)) {
+            return Err(format!("There is no edge between {} and {}.", x, y));
+        }
+        self.add(x, y, val)
+    }
+}
+
+impl<W: Clone> DynamicGraph<W> for UndirectedMatrix<W> {
+    fn add_vertex(&mut self) -> uint {
+        self.elements.grow(self.vertices + 1, &None);
+        self.vertices += 1;
+        self.vertices - 1
+    }
+    fn remove_vertex(&mut self, v: uint) -> Result<Vec<Option<uint>>, ~str> {
+        try!(self.index(v, v));
+        let remaining = self.vertices - 1;
+        let mut elements = Vec::with_capacity(remaining * (remaining + 1) / 2);
+        for high in range(0, self.vertices) {
+            for low in range(0, high + 1) {
+                if high != v && low != v {
+                    elements.push(self.elements.get(high * (high + 1) / 2 + low).clone());
+                }
+            }
+        }
+        self.elements = elements;
+        self.vertices = remaining;
+        Ok(removal_remap(remaining + 1, v))
+    }
+}
+
+#[test]
+fn test_undirected_symmetry() {
+    let mut graph: ~UndirectedMatrix<int> = MutableGraph::new(4);
+    assert_eq!(graph.elements.len(), 10);
+    assert!(graph.add(2, 0, 5).is_ok());
+    assert!(graph.add(1, 1, 3).is_ok());
+    assert!(graph.adjacent(0, 2).ok().unwrap());
+    assert_eq!(graph.get_edge_value(0, 2).ok().unwrap(), Some(5));
+    assert_eq!(graph.neighbors(0), vec!(2u));
+    assert_eq!(graph.neighbors(2), vec!(0u));
+    assert_eq!(graph.neighbors(1), vec!(1u));
+    assert!(graph.set_edge_value(0, 2, 6).is_ok());
+    assert_eq!(graph.get_edge_value(2, 0).ok().unwrap(), Some(6));
+    assert!(graph.set_edge_value(0, 3, 1).is_err());
+    assert_eq!(graph.delete(0, 2).ok().unwrap(), Some(6));
+    assert!(!graph.adjacent(2, 0).ok().unwrap());
+    assert!(graph.add(4, 0, 1).is_err());
+}
+
+#[test]
+fn test_undirected_add_remove_vertex() {
+    let mut graph: ~UndirectedMatrix<int> = MutableGraph::new(3);
+    assert!(graph.add(0, 1, 1).is_ok());
+    assert!(graph.add(1, 2, 2).is_ok());
+    assert_eq!(graph.add_vertex(), 3);
+    assert!(graph.add(3, 0, 3).is_ok());
+    assert_eq!(connected_components(&*graph), vec!(vec!(0u, 1, 2, 3)));
+    let remap = graph.remove_vertex(1).ok().unwrap();
+    assert_eq!(remap, vec!(Some(0u), None, Some(1), Some(2)));
+    assert_eq!(graph.elements.len(), 6);
+    assert_eq!(graph.get_edge_value(0, 2).ok().unwrap(), Some(3));
+    assert_eq!(connected_components(&*graph), vec!(vec!(0u, 2), vec!(1u)));
+}
@@ -0,0 +1,248 @@
+/*
+ * Christopher Piraino
+ *
+ * A disjoint set (union-find) structure
+ * over the elements 0..n, with union by
+ * rank, path compression and rollback.
+ */
+use std::vec::Vec;
+use graph::Graph;
+#[test]
+use graph::{MutableGraph, AdjacencyMatrix};
+
+// A write made while a snapshot is open, along with what it overwrote.
+enum Change {
+    // An element and its parent before path compression.
+    Compress(uint, uint),
+    // The root that was linked, the root it was linked under and that root's old rank.
+    Link(uint, uint, uint)
+}
+
+pub struct UnionFind {
+    parent: Vec<uint>,
+    // An upper bound on the height of each root's tree.
+    rank: Vec<uint>,
+    // The number of elements in the set of each root.
+    size: Vec<uint>,
+    count: uint,
+    // Changes since the oldest open snapshot, and where each snapshot starts.
+    history: Vec<Change>,
+    snapshots: Vec<uint>
+}
+
+impl UnionFind {
+    // Starts with every element in a set of its own.
+    pub fn new(elements: uint) -> UnionFind {
+        UnionFind {
+            parent: Vec::from_fn(elements, |i| i),
+            rank: Vec::from_elem(elements, 0u),
+            size: Vec::from_elem(elements, 1u),
+            count: elements,
+            history: Vec::new(),
+            snapshots: Vec::new()
+        }
+    }
+    // One set per weakly connected component of the graph, which for an
+    // undirected graph is one set per connected component.
+    pub fn from_graph<W, G: Graph<W>>(graph: &G) -> UnionFind {
+        let mut sets = UnionFind::new(graph.vertices());
+        for u in range(0, graph.vertices()) {
+            for &v in graph.neighbors(u).iter() {
+                sets.union(u, v);
+            }
+        }
+        sets
+    }
+    // The number of elements.
+    pub fn len(&self) -> uint {
+        self.parent.len()
+    }
+    // The number of disjoint sets.
+    pub fn count(&self) -> uint {
+        self.count
+    }
+    // Returns the representative of the set containing x, pointing
+    // every element on the way directly at it.
+    pub fn find(&mut self, x: uint) -> uint {
+        let mut root = x;
+        while *self.parent.get(root) != root {
+            root = *self.parent.get(root);
+        }
+        let mut current = x;
+        while current != root {
+            let next = *self.parent.get(current);
+            if next != root {
+                if !self.snapshots.is_empty() {
+                    self.history.push(Compress(current, next));
+                }
+                *self.parent.get_mut(current) = root;
+            }
+            current = next;
+        }
+        root
+    }
+    // Merges the sets containing x and y, hanging the shorter tree under
+    // the taller one. Returns false if they were already the same set.
+    pub fn union(&mut self, x: uint, y: uint) -> bool {
+        let (x_root, y_root) = (self.find(x), self.find(y));
+        if x_root == y_root {
+            return false;
+        }
+        let (x_rank, y_rank) = (*self.rank.get(x_root), *self.rank.get(y_root));
+        let (child, root) = if x_rank < y_rank { (x_root, y_root) } else { (y_root, x_root) };
+        if !self.snapshots.is_empty() {
+            self.history.push(Link(child, root, *self.rank.get(root)));
+        }
+        *self.parent.get_mut(child) = root;
+        if x_rank == y_rank {
+            *self.rank.get_mut(root) += 1;
+        }
+        let merged = *self.size.get(root) + *self.size.get(child);
+        *self.size.get_mut(root) = merged;
+        self.count -= 1;
+        true
+    }
+    pub fn connected(&mut self, x: uint, y: uint) -> bool {
+        self.find(x) == self.find(y)
+    }
+    // The number of elements in the set containing x.
+    pub fn size(&mut self, x: uint) -> uint {
+        let root = self.find(x);
+        *self.size.get(root)
+    }
+    // Every set as a sorted list of its elements, ordered by smallest element.
+    pub fn sets(&mut self) -> Vec<Vec<uint>> {
+        let mut index: Vec<Option<uint>> = Vec::from_elem(self.len(), None);
+        let mut sets: Vec<Vec<uint>> = Vec::new();
+        for x in range(0, self.len()) {
+            let root = self.find(x);
+            match *index.get(root) {
+                Some(i) => sets.get_mut(i).push(x),
+                None => {
+                    *index.get_mut(root) = Some(sets.len());
+                    sets.push(vec!(x));
+                }
+            }
+        }
+        sets
+    }
+    /*  Opens a snapshot. Every change made afterwards is recorded until the
+     *  snapshot is closed by rollback, which undoes them, or commit, which
+     *  keeps them. Snapshots nest, and rollback and commit always close the
+     *  most recently opened one.
+     */
+    pub fn snapshot(&mut self) {
+        self.snapshots.push(self.history.len());
+    }
+    pub fn rollback(&mut self) {
+        let start = match self.snapshots.pop() {
+            Some(start) => start,
+            None => fail!("No snapshot to roll back to.")
+        };
+        while self.history.len() > start {
+            match self.history.pop().unwrap() {
+                Compress(x, parent) => *self.parent.get_mut(x) = parent,
+                Link(child, root, rank) => {
+                    *self.parent.get_mut(child) = child;
+                    *self.rank.get_mut(root) = rank;
+                    let split = *self.size.get(root) - *self.size.get(child);
+                    *self.size.get_mut(root) = split;
+                    self.count += 1;
+                }
+            }
+        }
+    }
+    pub fn commit(&mut self) {
+        if self.snapshots.pop().is_none() {
+            fail!("No snapshot to commit.");
+        }
+        // Nothing can be rolled back any more once the last snapshot is closed.
+        if self.snapshots.is_empty() {
+            self.history.clear();
+        }
+    }
+}
+
+#[test]
+fn test_union_find() {
+    let mut sets = UnionFind::new(5);
+    assert!(sets.find(3) == 3);
+    assert!(sets.union(0, 1));
+    assert!(sets.union(3, 4));
+    assert!(sets.union(1, 4));
+    assert!(!sets.union(0, 3));
+    assert_eq!(sets.find(0), sets.find(4));
+    assert!(sets.find(2) != sets.find(0));
+}
+
+#[test]
+fn test_union_find_sizes_and_sets() {
+    let mut sets = UnionFind::new(6);
+    assert_eq!(sets.count(), 6);
+    sets.union(4, 1);
+    sets.union(5, 2);
+    sets.union(2, 4);
+    assert_eq!(sets.count(), 3);
+    assert_eq!(sets.size(1), 4);
+    assert_eq!(sets.size(3), 1);
+    assert!(sets.connected(5, 1));
+    assert!(!sets.connected(0, 1));
+    assert_eq!(sets.sets(), vec!(vec!(0u), vec!(1u, 2, 4, 5), vec!(3u)));
+}
+
+#[test]
+fn test_union_find_rollback() {
+    let mut sets = UnionFind::new(6);
+    sets.union(0, 1);
+    sets.snapshot();
+    sets.union(2, 3);
+    sets.union(1, 3);
+    sets.snapshot();
+    sets.union(4, 5);
+    sets.union(5, 0);
+    assert_eq!(sets.count(), 1);
+    sets.rollback();
+    assert_eq!(sets.count(), 3);
+    assert_eq!(sets.size(3), 4);
+    assert!(!sets.connected(4, 0));
+    sets.rollback();
+    assert_eq!(sets.count(), 5);
+    assert_eq!(sets.sets(), vec!(vec!(0u, 1), vec!(2u), vec!(3u), vec!(4u), vec!(5u)));
+}
+
+#[test]
+fn test_union_find_commit() {
+    let mut sets = UnionFind::new(4);
+    sets.snapshot();
+    sets.union(0, 1);
+    sets.snapshot();
+    sets.union(2, 3);
+    sets.commit();
+    sets.rollback();
+    assert_eq!(sets.count(), 4);
+    sets.snapshot();
+    sets.union(1, 2);
+    sets.commit();
+    assert_eq!(sets.count(), 3);
+    assert!(sets.history.is_empty());
+}
+
+/*
+ *  Graph:
+ *  V 0 1 2 3 4
+ *  0 0 1 0 0 0
+ *  1 0 0 0 0 0
+ *  2 0 0 0 0 0
+ *  3 0 0 1 0 0
+ *  4 0 0 0 0 0
+ *
+ */
+#[test]
+fn test_union_find_from_graph() {
+    let mut graph: ~AdjacencyMatrix<int> = MutableGraph::new(5);
+    assert!(graph.add(0,1,1).is_ok());
+    assert!(graph.add(3,2,1).is_ok());
+    let mut sets = UnionFind::from_graph(&*graph);
+    assert_eq!(sets.count(), 3);
+    assert_eq!(sets.sets(), vec!(vec!(0u, 1), vec!(2u, 3), vec!(4u)));
+}